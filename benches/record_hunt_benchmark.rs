@@ -1,9 +1,10 @@
+use lychrel_finder::hunt_filter::default_filters;
 use lychrel_finder::lychrel::{lychrel_iteration, lychrel_iteration_with_cache};
 use lychrel_finder::seed_generator::SeedGenerator;
 use lychrel_finder::thread_cache::ThreadCache;
-use lychrel_finder::{GeneratorMode, HuntConfig};
+use lychrel_finder::{GeneratorMode, HuntConfig, StopConditions};
 use num_bigint::BigUint;
-use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize};
+use std::sync::atomic::{AtomicU64, AtomicUsize};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -15,7 +16,7 @@ struct BenchmarkMetrics {
     config_name: String,
     duration_secs: f64,
     seeds_tested: u64,
-    best_iterations: u32,
+    best_iterations: u64,
     candidates_per_sec: f64,
     cache_hit_rate: f64,
 }
@@ -25,7 +26,7 @@ struct StatsWrapper {
     seeds_tested: AtomicU64,
     cache_hits: AtomicU64,
     cache_misses: AtomicU64,
-    best_iterations: AtomicU32,
+    best_iterations: AtomicU64,
     records_found: AtomicUsize,
 }
 
@@ -49,7 +50,7 @@ fn run_benchmark(
         seeds_tested: AtomicU64::new(0),
         cache_hits: AtomicU64::new(0),
         cache_misses: AtomicU64::new(0),
-        best_iterations: AtomicU32::new(0),
+        best_iterations: AtomicU64::new(0),
         records_found: AtomicUsize::new(0),
     });
 
@@ -332,8 +333,14 @@ fn main() {
                 cache_size: 50000,
                 generator_mode: GeneratorMode::Sequential,
                 checkpoint_interval: 0,
+                checkpoint_interval_secs: None,
+                stats_interval: 100_000,
+                stats_interval_secs: None,
                 checkpoint_file: "/dev/null".to_string(),
                 warmup: false,
+                radix: 10,
+                stop: StopConditions::default(),
+                filters: default_filters(),
             },
         ),
         (
@@ -347,8 +354,14 @@ fn main() {
                 cache_size: 50000,
                 generator_mode: GeneratorMode::Sequential,
                 checkpoint_interval: 0,
+                checkpoint_interval_secs: None,
+                stats_interval: 100_000,
+                stats_interval_secs: None,
                 checkpoint_file: "/dev/null".to_string(),
                 warmup: true,
+                radix: 10,
+                stop: StopConditions::default(),
+                filters: default_filters(),
             },
         ),
         (
@@ -362,8 +375,14 @@ fn main() {
                 cache_size: 1000000,
                 generator_mode: GeneratorMode::Sequential,
                 checkpoint_interval: 0,
+                checkpoint_interval_secs: None,
+                stats_interval: 100_000,
+                stats_interval_secs: None,
                 checkpoint_file: "/dev/null".to_string(),
                 warmup: false,
+                radix: 10,
+                stop: StopConditions::default(),
+                filters: default_filters(),
             },
         ),
         (
@@ -377,8 +396,14 @@ fn main() {
                 cache_size: 1000000,
                 generator_mode: GeneratorMode::Sequential,
                 checkpoint_interval: 0,
+                checkpoint_interval_secs: None,
+                stats_interval: 100_000,
+                stats_interval_secs: None,
                 checkpoint_file: "/dev/null".to_string(),
                 warmup: true,
+                radix: 10,
+                stop: StopConditions::default(),
+                filters: default_filters(),
             },
         ),
         (
@@ -392,8 +417,14 @@ fn main() {
                 cache_size: 1000000,
                 generator_mode: GeneratorMode::Sequential,
                 checkpoint_interval: 0,
+                checkpoint_interval_secs: None,
+                stats_interval: 100_000,
+                stats_interval_secs: None,
                 checkpoint_file: "/dev/null".to_string(),
                 warmup: false,
+                radix: 10,
+                stop: StopConditions::default(),
+                filters: default_filters(),
             },
         ),
         (
@@ -407,8 +438,14 @@ fn main() {
                 cache_size: 1000000,
                 generator_mode: GeneratorMode::Sequential,
                 checkpoint_interval: 0,
+                checkpoint_interval_secs: None,
+                stats_interval: 100_000,
+                stats_interval_secs: None,
                 checkpoint_file: "/dev/null".to_string(),
                 warmup: false,
+                radix: 10,
+                stop: StopConditions::default(),
+                filters: default_filters(),
             },
         ),
     ];
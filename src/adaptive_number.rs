@@ -0,0 +1,221 @@
+use crate::digit_number::DigitNumber;
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+
+/// Values below this bound can be reversed with u64 arithmetic
+///
+/// A value `n` with `k` digits satisfies `radix^k <= n * radix`, so its
+/// reverse (also below `radix^k`) fits whenever `n * radix` does.
+fn u64_limit(radix: u32) -> u128 {
+    (u64::MAX / radix as u64) as u128
+}
+
+/// Values below this bound can take a reverse-add step without overflowing
+///
+/// Both the value and its reverse stay below `radix^k <= n * radix < 2^127`,
+/// so their sum fits in a u128.
+fn u128_limit(radix: u32) -> u128 {
+    (u128::MAX / 2) / radix as u128
+}
+
+/// Reverse the digits of a machine word in the given radix
+///
+/// Uses u64 arithmetic when the value is small enough, since 128-bit
+/// division is noticeably slower.
+fn reverse_native(n: u128, radix: u32) -> u128 {
+    if n < u64_limit(radix) {
+        let radix = radix as u64;
+        let mut n = n as u64;
+        let mut reversed = 0u64;
+        while n > 0 {
+            reversed = reversed * radix + n % radix;
+            n /= radix;
+        }
+        reversed as u128
+    } else {
+        let radix = radix as u128;
+        let mut n = n;
+        let mut reversed = 0u128;
+        while n > 0 {
+            reversed = reversed * radix + n % radix;
+            n /= radix;
+        }
+        reversed
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Reverse-add steps taken on this thread, for tests that check work is skipped
+    pub(crate) static REVERSE_ADDS: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+/// Number used by the reverse-add engine, promoted on overflow
+///
+/// Values start out as native `u128` words (with a u64 fast path for the
+/// reversal) and are promoted to a `DigitNumber` the first time a step would
+/// leave the range where native arithmetic is exact. Promotion is one-way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdaptiveNumber {
+    Native {
+        value: u128,
+        reversed: u128,
+        radix: u32,
+    },
+    Digits(DigitNumber),
+}
+
+impl AdaptiveNumber {
+    /// Build the smallest decimal representation that can hold the number
+    pub fn from_biguint(n: &BigUint) -> Self {
+        Self::from_biguint_radix(n, 10)
+    }
+
+    /// Build the smallest representation in the given radix that can hold the number
+    pub fn from_biguint_radix(n: &BigUint, radix: u32) -> Self {
+        match n.to_u128() {
+            Some(value) => Self::from_u128_radix(value, radix),
+            None => AdaptiveNumber::Digits(DigitNumber::from_biguint_radix(n, radix)),
+        }
+    }
+
+    /// Build the smallest representation in the given radix from a machine word
+    pub fn from_u128_radix(value: u128, radix: u32) -> Self {
+        if value < u128_limit(radix) {
+            AdaptiveNumber::Native {
+                value,
+                reversed: reverse_native(value, radix),
+                radix,
+            }
+        } else {
+            AdaptiveNumber::Digits(DigitNumber::from_biguint_radix(
+                &BigUint::from(value),
+                radix,
+            ))
+        }
+    }
+
+    /// Convert back into a BigUint
+    pub fn to_biguint(&self) -> BigUint {
+        match self {
+            AdaptiveNumber::Native { value, .. } => BigUint::from(*value),
+            AdaptiveNumber::Digits(digits) => digits.to_biguint(),
+        }
+    }
+
+    /// Number of digits in the number's radix
+    pub fn len(&self) -> usize {
+        match self {
+            AdaptiveNumber::Native { value, radix, .. } => value
+                .checked_ilog(*radix as u128)
+                .map_or(1, |l| l as usize + 1),
+            AdaptiveNumber::Digits(digits) => digits.len(),
+        }
+    }
+
+    /// Check if the number has no digits (never true for a valid number)
+    pub fn is_empty(&self) -> bool {
+        match self {
+            AdaptiveNumber::Native { .. } => false,
+            AdaptiveNumber::Digits(digits) => digits.is_empty(),
+        }
+    }
+
+    /// Whether the value still lives in a machine word
+    pub fn is_native(&self) -> bool {
+        matches!(self, AdaptiveNumber::Native { .. })
+    }
+
+    /// Check if the number reads the same forwards and backwards
+    pub fn is_palindrome(&self) -> bool {
+        match self {
+            AdaptiveNumber::Native {
+                value, reversed, ..
+            } => value == reversed,
+            AdaptiveNumber::Digits(digits) => digits.is_palindrome(),
+        }
+    }
+
+    /// Add the number to its reverse, promoting to digits if the sum is too large
+    ///
+    /// # Returns
+    ///
+    /// `true` if the new value is a palindrome
+    pub fn reverse_add(&mut self) -> bool {
+        #[cfg(test)]
+        REVERSE_ADDS.with(|steps| steps.set(steps.get() + 1));
+
+        match self {
+            AdaptiveNumber::Native {
+                value,
+                reversed,
+                radix,
+            } => {
+                let sum = *value + *reversed;
+                if sum < u128_limit(*radix) {
+                    *value = sum;
+                    *reversed = reverse_native(sum, *radix);
+                    *value == *reversed
+                } else {
+                    let digits = DigitNumber::from_biguint_radix(&BigUint::from(sum), *radix);
+                    let palindrome = digits.is_palindrome();
+                    *self = AdaptiveNumber::Digits(digits);
+                    palindrome
+                }
+            }
+            AdaptiveNumber::Digits(digits) => digits.reverse_add(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reverse_native() {
+        assert_eq!(reverse_native(0, 10), 0);
+        assert_eq!(reverse_native(1200, 10), 21);
+        assert_eq!(
+            reverse_native(u64::MAX as u128, 10),
+            51_615_590_737_044_764_481
+        );
+        assert_eq!(reverse_native(0b10110, 2), 0b01101);
+        assert_eq!(reverse_native(0x1f0, 16), 0x0f1);
+    }
+
+    #[test]
+    fn test_matches_digit_engine_across_promotion() {
+        for start in [89u64, 196, 10677, 1186060307891929990] {
+            let big = BigUint::from(start);
+            let mut adaptive = AdaptiveNumber::from_biguint(&big);
+            let mut digits = DigitNumber::from_biguint(&big);
+            for _ in 0..120 {
+                assert_eq!(adaptive.reverse_add(), digits.reverse_add());
+                assert_eq!(adaptive.to_biguint(), digits.to_biguint());
+                assert_eq!(adaptive.len(), digits.len());
+            }
+            assert!(!adaptive.is_native());
+        }
+    }
+
+    #[test]
+    fn test_matches_digit_engine_in_other_radices() {
+        for radix in [2u32, 4, 11, 16, 36] {
+            let big = BigUint::from(1000u32);
+            let mut adaptive = AdaptiveNumber::from_biguint_radix(&big, radix);
+            let mut digits = DigitNumber::from_biguint_radix(&big, radix);
+            for _ in 0..200 {
+                assert_eq!(adaptive.reverse_add(), digits.reverse_add());
+                assert_eq!(adaptive.to_biguint(), digits.to_biguint());
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_start_uses_digits() {
+        let big: BigUint = "10000000000000000000000000000000000000001".parse().unwrap();
+        assert!(!AdaptiveNumber::from_biguint(&big).is_native());
+        assert!(AdaptiveNumber::from_biguint(&BigUint::from(196u32)).is_native());
+    }
+}
@@ -1,16 +1,28 @@
+use crate::pair_sum::{PairSumNumber, PairSumState};
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Checkpoint {
     pub start_number: BigUint,
-    pub current_number: BigUint,
+    /// Current value after iterations, absent when `pair_sums` holds it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_number: Option<BigUint>,
     pub iterations_completed: u64,
     pub max_iterations: u64,
     pub progress_interval: u64,
     pub checkpoint_interval: Option<u64>,
     pub elapsed_secs: f64,
     pub timestamp: String,
+    #[serde(default = "default_limb_threshold")]
+    pub limb_threshold: usize,
+    /// Pair-sum form of the current number, written by the pair-sum engine
+    #[serde(default)]
+    pub pair_sums: Option<PairSumState>,
+}
+
+fn default_limb_threshold() -> usize {
+    crate::verify::DEFAULT_LIMB_THRESHOLD
 }
 
 impl Checkpoint {
@@ -55,16 +67,35 @@ impl Checkpoint {
 
         Checkpoint {
             start_number,
-            current_number,
+            current_number: Some(current_number),
             iterations_completed,
             max_iterations,
             progress_interval,
             checkpoint_interval,
             elapsed_secs,
             timestamp,
+            limb_threshold: default_limb_threshold(),
+            pair_sums: None,
         }
     }
 
+    /// Set the digit count above which a resumed run uses the limb engine
+    pub fn with_limb_threshold(mut self, limb_threshold: usize) -> Self {
+        self.limb_threshold = limb_threshold;
+        self
+    }
+
+    /// Store the pair-sum form so a resumed run continues with the pair-sum engine
+    ///
+    /// The pair sums replace the current number, which is not saved with them.
+    pub fn with_pair_sums(mut self, pair_sums: Option<PairSumState>) -> Self {
+        if pair_sums.is_some() {
+            self.current_number = None;
+        }
+        self.pair_sums = pair_sums;
+        self
+    }
+
     /// Save checkpoint to a file
     ///
     /// Serializes checkpoint to JSON and writes it to the specified file path.
@@ -141,9 +172,14 @@ impl Checkpoint {
     ///
     /// # Returns
     ///
-    /// The number of decimal digits in the current number
+    /// The number of decimal digits in the current number, 0 if the
+    /// checkpoint holds neither a current number nor valid pair sums
     pub fn digit_count(&self) -> usize {
-        self.current_number.to_string().len()
+        match (&self.current_number, &self.pair_sums) {
+            (Some(n), _) => n.to_string().len(),
+            (None, Some(state)) => PairSumNumber::from_state(state).map_or(0, |n| n.len()),
+            (None, None) => 0,
+        }
     }
 
     /// Calculate remaining iterations
@@ -0,0 +1,163 @@
+use crate::adaptive_number::AdaptiveNumber;
+use crate::lychrel::IterationResult;
+use crate::thread_cache::{ThreadCache, ThreadInfo};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+/// How a tested number relates to the reverse-add threads around it
+///
+/// Potential Lychrel numbers are either *seeds*, the smallest number of
+/// their thread (OEIS A063048), or *kin* numbers whose thread merges into
+/// the thread of a smaller seed (the rest of OEIS A023108).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Classification {
+    /// The number is a palindrome before any iteration
+    PalindromeStart,
+    /// Potential Lychrel number that starts its own thread
+    Seed,
+    /// Potential Lychrel number that joins the thread of a smaller seed
+    Kin {
+        /// The seed whose thread is joined
+        seed: BigUint,
+        /// Iteration of this number at which its value first lies on the seed's thread
+        joined_at: u64,
+    },
+}
+
+/// Tag every potential Lychrel number with its seed or kin classification
+///
+/// Candidates are sorted by start number and walked in ascending order. Each
+/// seed's thread is stored in a `ThreadCache` for its whole length, so a later
+/// candidate is kin as soon as one of its values (including the start number
+/// itself) is found in the cache. Threads are only compared within
+/// `max_iterations`, and only against the given candidates: a number whose
+/// seed lies outside the searched range is reported as a seed.
+///
+/// # Arguments
+///
+/// * `candidates` - Potential Lychrel results, sorted in place
+/// * `max_iterations` - Number of iterations each thread is followed for
+/// * `radix` - Base the iteration is performed in
+///
+/// # Examples
+///
+/// ```
+/// use lychrel_finder::{classify_candidates, lychrel_iteration, Classification};
+/// use num_bigint::BigUint;
+///
+/// let mut candidates = vec![
+///     lychrel_iteration(BigUint::from(295u32), 100),
+///     lychrel_iteration(BigUint::from(196u32), 100),
+/// ];
+/// classify_candidates(&mut candidates, 100, 10);
+///
+/// assert_eq!(candidates[0].classification, Some(Classification::Seed));
+/// assert_eq!(
+///     candidates[1].classification,
+///     Some(Classification::Kin { seed: BigUint::from(196u32), joined_at: 1 })
+/// );
+/// ```
+pub fn classify_candidates(candidates: &mut [IterationResult], max_iterations: u64, radix: u32) {
+    candidates.sort_by(|a, b| a.start_number.cmp(&b.start_number));
+
+    let mut threads = ThreadCache::new(usize::MAX);
+    let mut path = Vec::new();
+
+    for candidate in candidates.iter_mut() {
+        if !candidate.is_potential_lychrel {
+            continue;
+        }
+
+        let mut current = AdaptiveNumber::from_biguint_radix(&candidate.start_number, radix);
+        let mut value = candidate.start_number.clone();
+        let mut iteration = 0;
+        let mut joined = None;
+        path.clear();
+
+        loop {
+            if let Some(info) = threads.check(&value) {
+                joined = Some((info, iteration));
+                break;
+            }
+            path.push(value);
+            if iteration >= max_iterations {
+                break;
+            }
+            current.reverse_add();
+            iteration += 1;
+            value = current.to_biguint();
+        }
+
+        let classification = match joined {
+            Some((info, joined_at)) => Classification::Kin {
+                seed: info.seed_number.parse().unwrap_or_default(),
+                joined_at,
+            },
+            None => Classification::Seed,
+        };
+
+        // Kin numbers only add the values before the join, the rest of
+        // their thread is already stored under the same seed
+        let seed_number = match &classification {
+            Classification::Kin { seed, .. } => seed.to_string(),
+            _ => candidate.start_number.to_string(),
+        };
+        threads.add_full_thread(
+            &path,
+            ThreadInfo {
+                seed_number,
+                iterations_from_seed: 0,
+                max_iterations_tested: max_iterations,
+                final_digits: current.len(),
+                reached_palindrome: false,
+                palindrome_at_iteration: None,
+            },
+        );
+
+        candidate.classification = Some(classification);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lychrel::lychrel_iteration;
+
+    fn candidates(numbers: &[u32], max_iterations: u64) -> Vec<IterationResult> {
+        numbers
+            .iter()
+            .map(|&n| lychrel_iteration(BigUint::from(n), max_iterations))
+            .collect()
+    }
+
+    #[test]
+    fn test_seeds_below_1000() {
+        // 196, 879 and 887 are Lychrel candidates below 1000; 887 lies on 196's thread
+        let mut results = candidates(&[887, 879, 196], 500);
+        classify_candidates(&mut results, 500, 10);
+
+        assert_eq!(results[0].classification, Some(Classification::Seed));
+        assert_eq!(results[1].classification, Some(Classification::Seed));
+        assert_eq!(
+            results[2].classification,
+            Some(Classification::Kin {
+                seed: BigUint::from(196u32),
+                joined_at: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_kin_join_iteration() {
+        // 295 + 592 = 887, which is 196's first iteration
+        let mut results = candidates(&[196, 295, 592], 200);
+        classify_candidates(&mut results, 200, 10);
+
+        for result in &results[1..] {
+            assert!(matches!(
+                &result.classification,
+                Some(Classification::Kin { seed, joined_at: 1 }) if *seed == BigUint::from(196u32)
+            ));
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use crate::lychrel::IterationResult;
+use crate::search::{search_range, SearchConfig};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::time::Instant;
+
+/// Numbers handed to `search_range` at a time
+const BLOCK_SIZE: u64 = 10_000;
+
+/// Range and output files of a smallest-number-per-delay search
+pub struct DelayConfig {
+    pub start: BigUint,
+    pub end: BigUint,
+    /// Largest iteration count to tabulate, also the iteration limit of every test
+    pub max_delay: u64,
+    pub radix: u32,
+    pub parallel: bool,
+    /// Save the table to `checkpoint_file` about every N numbers tested
+    pub checkpoint_interval: Option<u64>,
+    pub checkpoint_file: Option<String>,
+    /// Text file receiving one `k number` line for each entry as it is found
+    pub table_file: Option<String>,
+}
+
+/// A number needing more iterations than every number before it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelayRecord {
+    pub number: BigUint,
+    pub delay: u64,
+}
+
+/// Smallest number for each delay, found so far
+///
+/// The delay of a number is the count of reverse-add steps it needs to
+/// reach a palindrome. Numbers that are palindromes already, and numbers
+/// needing more than `max_delay` steps, are not part of the table. The
+/// table doubles as the checkpoint of its search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelayTable {
+    pub start: BigUint,
+    pub end: BigUint,
+    pub max_delay: u64,
+    pub radix: u32,
+    /// First number not searched yet
+    pub next: BigUint,
+    pub numbers_tested: u64,
+    /// Smallest number needing exactly `k` iterations, by `k`
+    pub smallest: BTreeMap<u64, BigUint>,
+    /// Every number whose delay beat all smaller numbers, in order
+    pub records: Vec<DelayRecord>,
+    pub elapsed_secs: f64,
+    pub timestamp: String,
+}
+
+impl DelayTable {
+    pub fn new(config: &DelayConfig) -> Self {
+        DelayTable {
+            start: config.start.clone(),
+            end: config.end.clone(),
+            max_delay: config.max_delay,
+            radix: config.radix,
+            next: config.start.clone(),
+            numbers_tested: 0,
+            smallest: BTreeMap::new(),
+            records: Vec::new(),
+            elapsed_secs: 0.0,
+            timestamp: String::new(),
+        }
+    }
+
+    /// True once the range is done or every delay up to `max_delay` has a number
+    pub fn is_complete(&self) -> bool {
+        self.next > self.end || self.smallest.len() as u64 >= self.max_delay
+    }
+
+    /// Delays from 1 to `max_delay` without a number yet
+    pub fn missing(&self) -> Vec<u64> {
+        (1..=self.max_delay)
+            .filter(|k| !self.smallest.contains_key(k))
+            .collect()
+    }
+
+    /// Whether this table was started with the same range, limit and base
+    pub fn matches(&self, config: &DelayConfig) -> bool {
+        self.start == config.start
+            && self.end == config.end
+            && self.max_delay == config.max_delay
+            && self.radix == config.radix
+    }
+
+    pub fn save(&self, filepath: &str) -> io::Result<()> {
+        crate::io_utils::save_to_file_str(self, filepath)
+    }
+
+    pub fn load(filepath: &str) -> io::Result<Self> {
+        crate::io_utils::load_from_file_str(filepath)
+    }
+
+    /// Take the result of the next number in ascending order
+    ///
+    /// Returns true if the number is the first found for its delay.
+    fn record(&mut self, result: &IterationResult) -> bool {
+        if !result.is_palindrome || result.iterations == 0 {
+            return false;
+        }
+        if self
+            .records
+            .last()
+            .is_none_or(|record| result.iterations > record.delay)
+        {
+            self.records.push(DelayRecord {
+                number: result.start_number.clone(),
+                delay: result.iterations,
+            });
+        }
+        if self.smallest.contains_key(&result.iterations) {
+            return false;
+        }
+        self.smallest
+            .insert(result.iterations, result.start_number.clone());
+        true
+    }
+
+    /// Entries in the order they were found, which is ascending by number
+    fn entries_by_number(&self) -> Vec<(u64, &BigUint)> {
+        let mut entries: Vec<_> = self.smallest.iter().map(|(&k, n)| (k, n)).collect();
+        entries.sort_by(|a, b| a.1.cmp(b.1));
+        entries
+    }
+}
+
+/// Find the smallest number needing exactly k iterations, for every k up to a limit
+///
+/// Numbers are tested in ascending blocks through `search_range`, so the
+/// first number seen with a given delay is the smallest in the range. The
+/// search stops at the end of the range or as soon as every delay up to
+/// `max_delay` has a number. When the range starts at 1 the table is OEIS
+/// A023109 (base 10), and the records are the numbers where the largest
+/// delay so far goes up.
+///
+/// # Arguments
+///
+/// * `config` - Range, delay limit, base and output files
+/// * `progress_callback` - Called with the table after every block, and whether a checkpoint was saved
+///
+/// # Returns
+///
+/// The finished table, or the error of writing the table file
+///
+/// # Examples
+///
+/// ```
+/// use lychrel_finder::delay_search::{search_delays, DelayConfig};
+/// use num_bigint::BigUint;
+///
+/// let table = search_delays(
+///     &DelayConfig {
+///         start: BigUint::from(1u32),
+///         end: BigUint::from(200u32),
+///         max_delay: 30,
+///         radix: 10,
+///         parallel: false,
+///         checkpoint_interval: None,
+///         checkpoint_file: None,
+///         table_file: None,
+///     },
+///     |_, _| {},
+/// )
+/// .unwrap();
+///
+/// assert_eq!(table.smallest[&1], BigUint::from(10u32));
+/// assert_eq!(table.smallest[&24], BigUint::from(89u32));
+/// ```
+pub fn search_delays<F>(config: &DelayConfig, progress_callback: F) -> io::Result<DelayTable>
+where
+    F: FnMut(&DelayTable, bool),
+{
+    resume_delay_search(config, DelayTable::new(config), progress_callback)
+}
+
+/// Continue a smallest-number-per-delay search from a saved table
+///
+/// The table file, if any, is rewritten from the saved table first, so
+/// lines written after the last checkpoint are not repeated.
+///
+/// # Arguments
+///
+/// * `config` - The configuration the table was started with
+/// * `table` - Table loaded from the checkpoint file
+/// * `progress_callback` - Called with the table after every block, and whether a checkpoint was saved
+///
+/// # Returns
+///
+/// The finished table, or the table so far after a stop request, or an
+/// error if the table belongs to another search or the table file cannot be
+/// written
+pub fn resume_delay_search<F>(
+    config: &DelayConfig,
+    mut table: DelayTable,
+    mut progress_callback: F,
+) -> io::Result<DelayTable>
+where
+    F: FnMut(&DelayTable, bool),
+{
+    if !table.matches(config) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "checkpoint belongs to a search with another range, limit or base",
+        ));
+    }
+
+    let radix = table.radix;
+    let mut table_file = match config.table_file {
+        Some(ref path) => {
+            let mut writer = BufWriter::new(File::create(path)?);
+            for (k, number) in table.entries_by_number() {
+                writeln!(writer, "{} {}", k, number.to_str_radix(radix))?;
+            }
+            writer.flush()?;
+            Some(writer)
+        }
+        None => None,
+    };
+
+    let start_time = Instant::now();
+    let base_elapsed = table.elapsed_secs;
+    let mut last_checkpoint = table.numbers_tested;
+
+    while !table.is_complete() {
+        let block_end = (&table.next + BLOCK_SIZE - 1u32).min(table.end.clone());
+        let results = search_range(SearchConfig {
+            start: table.next.clone(),
+            end: block_end.clone(),
+            max_iterations: table.max_delay,
+            radix,
+            classify: false,
+            parallel: config.parallel,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            cache_size: None,
+        });
+
+        // Palindromes come back in ascending order, so first seen is smallest
+        for result in &results.palindromes_found {
+            if table.record(result) {
+                if let Some(ref mut writer) = table_file {
+                    writeln!(
+                        writer,
+                        "{} {}",
+                        result.iterations,
+                        result.start_number.to_str_radix(radix)
+                    )?;
+                }
+            }
+        }
+        if let Some(ref mut writer) = table_file {
+            writer.flush()?;
+        }
+
+        table.numbers_tested += results.total_tested;
+        table.next = block_end + 1u32;
+        table.elapsed_secs = base_elapsed + start_time.elapsed().as_secs_f64();
+
+        // A stop request saves the table and ends the search after this block
+        let stop = config.checkpoint_file.is_some() && crate::interrupt::stop_requested();
+        let should_save_checkpoint = stop
+            || match config.checkpoint_interval {
+                Some(interval) => table.numbers_tested - last_checkpoint >= interval,
+                None => false,
+            };
+        let mut saved = false;
+        if should_save_checkpoint {
+            if let Some(ref file) = config.checkpoint_file {
+                table.timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+                if let Err(e) = table.save(file) {
+                    eprintln!("Warning: Failed to save checkpoint: {}", e);
+                } else {
+                    last_checkpoint = table.numbers_tested;
+                    saved = true;
+                }
+            }
+        }
+        progress_callback(&table, saved);
+        if stop {
+            break;
+        }
+    }
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lychrel::lychrel_iteration_radix;
+
+    fn config(end: u32, max_delay: u64) -> DelayConfig {
+        DelayConfig {
+            start: BigUint::from(1u32),
+            end: BigUint::from(end),
+            max_delay,
+            radix: 10,
+            parallel: true,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            table_file: None,
+        }
+    }
+
+    #[test]
+    fn test_smallest_per_delay_matches_brute_force() {
+        let table = search_delays(&config(5000, 24), |_, _| {}).unwrap();
+
+        let mut smallest = BTreeMap::new();
+        let mut records: Vec<DelayRecord> = Vec::new();
+        for n in 1..=5000u32 {
+            let result = lychrel_iteration_radix(BigUint::from(n), 24, 10);
+            if !result.is_palindrome || result.iterations == 0 {
+                continue;
+            }
+            smallest
+                .entry(result.iterations)
+                .or_insert_with(|| BigUint::from(n));
+            if records.last().is_none_or(|r| result.iterations > r.delay) {
+                records.push(DelayRecord {
+                    number: BigUint::from(n),
+                    delay: result.iterations,
+                });
+            }
+        }
+
+        assert_eq!(table.smallest, smallest);
+        assert_eq!(table.records, records);
+        assert_eq!(table.smallest[&1], BigUint::from(10u32));
+        assert_eq!(table.smallest[&24], BigUint::from(89u32));
+        assert_eq!(table.numbers_tested, 5000);
+    }
+
+    #[test]
+    fn test_resumed_delay_search_matches_uninterrupted() {
+        let dir = std::env::temp_dir();
+        let table_path = dir.join("lychrel_delay_table.txt");
+        let table_path = table_path.to_str().unwrap().to_string();
+
+        let full = search_delays(&config(30_000, 30), |_, _| {}).unwrap();
+
+        // What a checkpoint of the full search holds after 10000 numbers
+        let first = search_delays(&config(10_000, 30), |_, _| {}).unwrap();
+        let checkpoint = DelayTable {
+            end: BigUint::from(30_000u32),
+            ..first
+        };
+
+        // Lines written after the checkpoint are replaced on resume
+        std::fs::write(&table_path, "1 10\n2 19\n99 12345\n").unwrap();
+        let mut resumed_config = config(30_000, 30);
+        resumed_config.table_file = Some(table_path.clone());
+        let resumed = resume_delay_search(&resumed_config, checkpoint, |_, _| {}).unwrap();
+        let written = std::fs::read_to_string(&table_path).unwrap();
+        std::fs::remove_file(&table_path).ok();
+
+        assert_eq!(resumed.smallest, full.smallest);
+        assert_eq!(resumed.records, full.records);
+        assert_eq!(resumed.numbers_tested, full.numbers_tested);
+        let expected: Vec<String> = full
+            .entries_by_number()
+            .iter()
+            .map(|(k, n)| format!("{} {}", k, n))
+            .collect();
+        assert_eq!(written.lines().collect::<Vec<_>>(), expected);
+
+        assert!(resume_delay_search(&config(100, 30), resumed, |_, _| {}).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use num_bigint::BigUint;
+use std::fmt;
+
+/// Smallest supported radix
+pub const MIN_RADIX: u32 = 2;
+
+/// Largest supported radix (digits are printed as 0-9 then a-z)
+pub const MAX_RADIX: u32 = 36;
+
+/// A number stored as a little-endian vector of digits in a given radix
+///
+/// This is the working representation of the reverse-add engine. Adding a
+/// number to its reverse only needs the digits themselves, so keeping them
+/// in a flat vector avoids the base conversions a `BigUint` would require on
+/// every iteration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigitNumber {
+    digits: Vec<u8>,
+    radix: u8,
+}
+
+impl DigitNumber {
+    /// Build a decimal digit vector from a BigUint
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lychrel_finder::digit_number::DigitNumber;
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = DigitNumber::from_biguint(&BigUint::from(196u32));
+    /// assert_eq!(n.len(), 3);
+    /// ```
+    pub fn from_biguint(n: &BigUint) -> Self {
+        Self::from_biguint_radix(n, 10)
+    }
+
+    /// Build a digit vector in the given radix from a BigUint
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is outside `MIN_RADIX..=MAX_RADIX`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lychrel_finder::digit_number::DigitNumber;
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = DigitNumber::from_biguint_radix(&BigUint::from(22u32), 2);
+    /// assert_eq!(n.to_string(), "10110");
+    /// ```
+    pub fn from_biguint_radix(n: &BigUint, radix: u32) -> Self {
+        assert!(
+            (MIN_RADIX..=MAX_RADIX).contains(&radix),
+            "radix must be between {} and {}",
+            MIN_RADIX,
+            MAX_RADIX
+        );
+        DigitNumber {
+            digits: n.to_radix_le(radix),
+            radix: radix as u8,
+        }
+    }
+
+    /// Wrap little-endian digits that are already valid in `radix`
+    pub(crate) fn from_digits_le(digits: Vec<u8>, radix: u32) -> Self {
+        DigitNumber {
+            digits,
+            radix: radix as u8,
+        }
+    }
+
+    /// Convert the digit vector back into a BigUint
+    pub fn to_biguint(&self) -> BigUint {
+        BigUint::from_radix_le(&self.digits, self.radix as u32).unwrap()
+    }
+
+    /// Radix the digits are expressed in
+    pub fn radix(&self) -> u32 {
+        self.radix as u32
+    }
+
+    /// Number of digits
+    pub fn len(&self) -> usize {
+        self.digits.len()
+    }
+
+    /// Check if the digit vector is empty (never true for a valid number)
+    pub fn is_empty(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Digits in little-endian order (least significant first)
+    pub fn digits(&self) -> &[u8] {
+        &self.digits
+    }
+
+    /// Return a new number with the digits in reverse order
+    pub fn reversed(&self) -> Self {
+        let mut digits: Vec<u8> = self.digits.iter().rev().copied().collect();
+        // Leading zeros of the reverse (trailing zeros of the original) are dropped
+        while digits.len() > 1 && digits[digits.len() - 1] == 0 {
+            digits.pop();
+        }
+        DigitNumber {
+            digits,
+            radix: self.radix,
+        }
+    }
+
+    /// Check if the number reads the same forwards and backwards
+    pub fn is_palindrome(&self) -> bool {
+        let len = self.digits.len();
+        (0..len / 2).all(|i| self.digits[i] == self.digits[len - 1 - i])
+    }
+
+    /// Number of carries produced when adding the number to its reverse
+    ///
+    /// Counts every digit position that passes a carry to the next one,
+    /// including a final carry that lengthens the sum.
+    pub fn reverse_add_carries(&self) -> usize {
+        let len = self.digits.len();
+        let mut carry = 0u8;
+        let mut carries = 0;
+        for i in 0..len {
+            if self.digits[i] + self.digits[len - 1 - i] + carry >= self.radix {
+                carry = 1;
+                carries += 1;
+            } else {
+                carry = 0;
+            }
+        }
+        carries
+    }
+
+    /// Add the number to its reverse in place
+    ///
+    /// The lower half is finalized first while the symmetric pair sums are
+    /// parked in the upper half, then the upper half is finalized from the
+    /// middle outwards and compared against its already final mirror digit.
+    /// The addition and the palindrome check therefore share a single pass
+    /// over the digits.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the new value is a palindrome
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lychrel_finder::digit_number::DigitNumber;
+    /// use num_bigint::BigUint;
+    ///
+    /// let mut n = DigitNumber::from_biguint(&BigUint::from(196u32));
+    /// assert!(!n.reverse_add());
+    /// assert_eq!(n.to_biguint(), BigUint::from(887u32));
+    /// ```
+    pub fn reverse_add(&mut self) -> bool {
+        let len = self.digits.len();
+        let half = len / 2;
+        let radix = self.radix;
+        let d = &mut self.digits;
+        let mut carry = 0u8;
+
+        // Lower half: a digit is final once the carry from below is known
+        for i in 0..half {
+            let j = len - 1 - i;
+            let sum = d[i] + d[j];
+            d[j] = sum;
+            let v = sum + carry;
+            if v >= radix {
+                d[i] = v - radix;
+                carry = 1;
+            } else {
+                d[i] = v;
+                carry = 0;
+            }
+        }
+
+        // Middle digit of an odd-length number is added to itself
+        if len % 2 == 1 {
+            let v = d[half] * 2 + carry;
+            if v >= radix {
+                d[half] = v - radix;
+                carry = 1;
+            } else {
+                d[half] = v;
+                carry = 0;
+            }
+        }
+
+        // Upper half: resolve parked pair sums and check against the mirror
+        let mut palindrome = true;
+        for j in (len - half)..len {
+            let v = d[j] + carry;
+            if v >= radix {
+                d[j] = v - radix;
+                carry = 1;
+            } else {
+                d[j] = v;
+                carry = 0;
+            }
+            if d[j] != d[len - 1 - j] {
+                palindrome = false;
+            }
+        }
+
+        if carry > 0 {
+            // The number grew by one digit, so the mirror positions shifted
+            d.push(1);
+            return self.is_palindrome();
+        }
+
+        palindrome
+    }
+}
+
+impl fmt::Display for DigitNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s: String = self
+            .digits
+            .iter()
+            .rev()
+            .map(|&d| std::char::from_digit(d as u32, self.radix as u32).unwrap())
+            .collect();
+        f.write_str(&s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lychrel::reverse_number;
+
+    #[test]
+    fn test_roundtrip() {
+        for n in [0u64, 7, 10, 196, 1000, 123456789] {
+            let big = BigUint::from(n);
+            assert_eq!(DigitNumber::from_biguint(&big).to_biguint(), big);
+        }
+    }
+
+    #[test]
+    fn test_reverse_add_matches_biguint() {
+        for n in [1u64, 9, 10, 56, 89, 196, 1186060307891929990] {
+            let mut current = BigUint::from(n);
+            let mut digits = DigitNumber::from_biguint(&current);
+            for _ in 0..30 {
+                let reversed = reverse_number(&current);
+                current += reversed;
+                let palindrome = digits.reverse_add();
+                assert_eq!(digits.to_biguint(), current);
+                assert_eq!(palindrome, current == reverse_number(&current));
+            }
+        }
+    }
+
+    #[test]
+    fn test_display_and_reversed() {
+        let n = DigitNumber::from_biguint(&BigUint::from(1200u32));
+        assert_eq!(n.to_string(), "1200");
+        assert_eq!(n.reversed().to_string(), "21");
+    }
+
+    #[test]
+    fn test_reverse_add_base_2() {
+        // 10110 (22) is a proven base 2 Lychrel number: 10110 + 01101 = 100011
+        let mut n = DigitNumber::from_biguint_radix(&BigUint::from(22u32), 2);
+        assert!(!n.reverse_add());
+        assert_eq!(n.to_string(), "100011");
+        assert_eq!(n.to_biguint(), BigUint::from(35u32));
+    }
+
+    #[test]
+    fn test_reverse_add_carries() {
+        // 196 + 691: 6+1, 9+9 carries, 1+6+1
+        assert_eq!(
+            DigitNumber::from_biguint(&BigUint::from(196u32)).reverse_add_carries(),
+            1
+        );
+        // 89 + 98 = 187 carries out of both positions
+        assert_eq!(
+            DigitNumber::from_biguint(&BigUint::from(89u32)).reverse_add_carries(),
+            2
+        );
+        assert_eq!(
+            DigitNumber::from_biguint(&BigUint::from(123u32)).reverse_add_carries(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_palindrome_after_carry() {
+        // 56 + 65 = 121: palindrome with a carry out of the top digit
+        let mut n = DigitNumber::from_biguint(&BigUint::from(56u32));
+        assert!(n.reverse_add());
+        assert_eq!(n.to_biguint(), BigUint::from(121u32));
+    }
+}
@@ -0,0 +1,216 @@
+use crate::lychrel::{lychrel_iteration_radix, IterationResult};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One stage of the pre-filter a record hunt runs before the full test
+///
+/// Stages run in order and the first one rejecting a number ends its test,
+/// unless the stage is a dry run: then the rejection is only counted and
+/// the number goes on, so the hunt can tell how many records the stage
+/// would have thrown away.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilterStage {
+    #[serde(flatten)]
+    pub kind: FilterKind,
+    /// Count the numbers this stage rejects but test them anyway
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// The check a filter stage makes on the first reverse-add steps of a number
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FilterKind {
+    /// Reject numbers growing by fewer than `min_bits` bits in `iterations` steps
+    ///
+    /// 66 bits in 50 steps is about 0.4 decimal digits per step.
+    Growth { iterations: u64, min_bits: u64 },
+    /// Reject numbers reaching a palindrome within `iterations` steps
+    EarlyPalindrome { iterations: u64 },
+}
+
+/// Numbers a filter stage let through and rejected
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageCounts {
+    pub passed: u64,
+    pub rejected: u64,
+    /// Rejected numbers that turned out to be records, only known for dry runs
+    pub rejected_records: u64,
+}
+
+impl FilterStage {
+    pub fn new(kind: FilterKind) -> Self {
+        FilterStage {
+            kind,
+            dry_run: false,
+        }
+    }
+}
+
+impl FilterKind {
+    /// Reverse-add steps the check looks at
+    pub fn iterations(&self) -> u64 {
+        match self {
+            FilterKind::Growth { iterations, .. } | FilterKind::EarlyPalindrome { iterations } => {
+                *iterations
+            }
+        }
+    }
+
+    /// Whether `candidate` passes, given the result of its first `iterations()` steps
+    fn passes(&self, candidate: &BigUint, quick: &IterationResult) -> bool {
+        match self {
+            FilterKind::Growth { min_bits, .. } => {
+                let end_bits = quick.final_number.as_ref().map(|n| n.bits()).unwrap_or(0);
+                end_bits as i64 - candidate.bits() as i64 >= *min_bits as i64
+            }
+            FilterKind::EarlyPalindrome { .. } => !quick.is_palindrome,
+        }
+    }
+}
+
+impl fmt::Display for FilterStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            FilterKind::Growth {
+                iterations,
+                min_bits,
+            } => write!(f, "growth of {} bits in {} steps", min_bits, iterations)?,
+            FilterKind::EarlyPalindrome { iterations } => {
+                write!(f, "palindrome within {} steps", iterations)?
+            }
+        }
+        if self.dry_run {
+            write!(f, " (dry run)")?;
+        }
+        Ok(())
+    }
+}
+
+/// The pre-filter hunts used before it was configurable
+///
+/// Both stages look at the same 50 steps, which are computed once.
+pub fn default_filters() -> Vec<FilterStage> {
+    vec![
+        FilterStage::new(FilterKind::Growth {
+            iterations: 50,
+            min_bits: 66,
+        }),
+        FilterStage::new(FilterKind::EarlyPalindrome { iterations: 50 }),
+    ]
+}
+
+/// Run a number through the filter stages
+///
+/// # Arguments
+///
+/// * `candidate` - The number to check
+/// * `stages` - The pipeline, in order
+/// * `counts` - Counters of each stage, updated in place
+/// * `radix` - Base of the hunt
+///
+/// # Returns
+///
+/// `None` if a stage rejected the number, or the indices of the dry-run
+/// stages that would have rejected it
+pub fn apply_filters(
+    candidate: &BigUint,
+    stages: &[FilterStage],
+    counts: &mut [StageCounts],
+    radix: u32,
+) -> Option<Vec<usize>> {
+    // Consecutive stages looking at the same number of steps share the run
+    let mut quick: Option<(u64, IterationResult)> = None;
+    let mut dry_rejections = Vec::new();
+
+    for (i, (stage, counts)) in stages.iter().zip(counts.iter_mut()).enumerate() {
+        let iterations = stage.kind.iterations();
+        let result = match quick.take().filter(|(n, _)| *n == iterations) {
+            Some((_, result)) => result,
+            None => lychrel_iteration_radix(candidate.clone(), iterations, radix),
+        };
+        let passes = stage.kind.passes(candidate, &result);
+        quick = Some((iterations, result));
+
+        if passes {
+            counts.passed += 1;
+        } else {
+            counts.rejected += 1;
+            if !stage.dry_run {
+                return None;
+            }
+            dry_rejections.push(i);
+        }
+    }
+    Some(dry_rejections)
+}
+
+/// Add the counters of another worker, stage by stage
+pub fn merge_counts(into: &mut [StageCounts], from: &[StageCounts]) {
+    for (a, b) in into.iter_mut().zip(from) {
+        a.passed += b.passed;
+        a.rejected += b.rejected;
+        a.rejected_records += b.rejected_records;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_filters_reject_slow_growth_and_early_palindromes() {
+        let stages = default_filters();
+        let mut counts = vec![StageCounts::default(); stages.len()];
+
+        // 89 reaches a palindrome after 24 steps, without growing enough
+        assert_eq!(
+            apply_filters(&BigUint::from(89u32), &stages, &mut counts, 10),
+            None
+        );
+        // 196 grows by about 21 digits in 50 steps
+        assert_eq!(
+            apply_filters(&BigUint::from(196u32), &stages, &mut counts, 10),
+            Some(vec![])
+        );
+
+        assert_eq!(counts[0].passed, 1);
+        assert_eq!(counts[0].rejected, 1);
+        assert_eq!(counts[1].passed, 1);
+        assert_eq!(counts[1].rejected, 0);
+    }
+
+    #[test]
+    fn test_dry_run_stage_counts_without_rejecting() {
+        let stages = vec![
+            FilterStage {
+                dry_run: true,
+                ..FilterStage::new(FilterKind::EarlyPalindrome { iterations: 30 })
+            },
+            FilterStage::new(FilterKind::EarlyPalindrome { iterations: 10 }),
+        ];
+        let mut counts = vec![StageCounts::default(); stages.len()];
+
+        // 89 fails the dry run but passes the 10-step stage
+        assert_eq!(
+            apply_filters(&BigUint::from(89u32), &stages, &mut counts, 10),
+            Some(vec![0])
+        );
+        assert_eq!(counts[0].rejected, 1);
+        assert_eq!(counts[1].passed, 1);
+    }
+
+    #[test]
+    fn test_stage_config_format() {
+        let json = r#"[
+            {"type": "growth", "iterations": 50, "min_bits": 66},
+            {"type": "early_palindrome", "iterations": 50, "dry_run": true}
+        ]"#;
+        let stages: Vec<FilterStage> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(stages[0], default_filters()[0]);
+        assert_eq!(stages[1].kind, default_filters()[1].kind);
+        assert!(stages[1].dry_run);
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether a stop was requested
+///
+/// Searches, hunts and verifications check this at their batch boundaries
+/// (every number, chunk, batch or iteration), save a final checkpoint and
+/// return early.
+pub fn stop_requested() -> bool {
+    STOP_REQUESTED.load(Ordering::Relaxed)
+}
+
+/// Ask running searches, hunts and verifications to stop at the next boundary
+pub fn request_stop() {
+    STOP_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+/// Forget an earlier stop request
+pub fn clear_stop() {
+    STOP_REQUESTED.store(false, Ordering::Relaxed);
+}
+
+/// Turn SIGINT and SIGTERM into stop requests
+///
+/// The first signal requests a stop, so the running command can write its
+/// final checkpoint. A second signal exits at once with status 130.
+///
+/// # Returns
+///
+/// An error if a handler was installed before
+pub fn install_handler() -> Result<(), ctrlc::Error> {
+    ctrlc::set_handler(|| {
+        if STOP_REQUESTED.swap(true, Ordering::SeqCst) {
+            eprintln!("\nSecond interrupt, exiting immediately");
+            std::process::exit(130);
+        }
+        eprintln!(
+            "\nInterrupt received, stopping and saving a checkpoint (press Ctrl-C again to exit immediately)"
+        );
+    })
+}
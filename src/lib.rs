@@ -1,28 +1,66 @@
+pub mod adaptive_number;
 pub mod checkpoint;
+pub mod classification;
+pub mod delay_search;
+pub mod digit_number;
+pub mod hunt_filter;
+pub mod interrupt;
 pub mod io_utils;
+pub mod limb_number;
 pub mod lychrel;
+pub mod number_source;
+pub mod pair_sum;
 pub mod record_checkpoint;
 pub mod record_hunt;
 pub mod search;
 pub mod search_checkpoint;
+pub mod search_passes;
+pub mod search_stats;
+pub mod search_stream;
 pub mod seed_generator;
 pub mod thread_cache;
+pub mod trace;
+pub mod trajectory;
 pub mod verify;
 
+pub use adaptive_number::AdaptiveNumber;
 pub use checkpoint::Checkpoint;
+pub use classification::{classify_candidates, Classification};
+pub use delay_search::{resume_delay_search, search_delays, DelayConfig, DelayRecord, DelayTable};
+pub use digit_number::DigitNumber;
+pub use hunt_filter::{FilterKind, FilterStage, StageCounts};
+pub use limb_number::LimbNumber;
 pub use lychrel::{
-    is_palindrome, lychrel_iteration, lychrel_iteration_with_cache, reverse_number, IterationResult,
+    is_palindrome, is_palindrome_radix, lychrel_iteration, lychrel_iteration_radix,
+    lychrel_iteration_with_cache, lychrel_iteration_with_cache_radix, reverse_number,
+    reverse_number_radix, IterationResult,
 };
+pub use number_source::{search_source, search_source_resumable, NumberSource};
+pub use pair_sum::{PairSumNumber, PairSumState};
 pub use record_checkpoint::{CheckpointConfig, GeneratorState, RecordHuntCheckpoint};
-pub use record_hunt::{HuntConfig, HuntResults, HuntStatistics, RecordCandidate, RecordHunter};
+pub use record_hunt::{
+    HuntConfig, HuntResults, HuntStatistics, RecordCandidate, RecordHunter, StopConditions,
+    StopReason,
+};
 pub use search::{
-    resume_search_from_checkpoint, search_range, search_range_resumable, SearchConfig,
+    parse_bound, resume_search_from_checkpoint, search_range, search_range_resumable, SearchConfig,
     SearchResults,
 };
 pub use search_checkpoint::SearchCheckpoint;
+pub use search_passes::{
+    parse_schedule, search_multi_pass, MultiPassConfig, MultiPassResults, SearchPass,
+};
+pub use search_stats::SearchStatistics;
+pub use search_stream::{
+    search_range_streaming, CallbackSink, NdjsonSink, NoResults, ResultSink, SearchSummary,
+};
 pub use seed_generator::{GeneratorMode, SeedGenerator};
 pub use thread_cache::{ThreadCache, ThreadInfo};
+pub use trace::{NoTrace, StdoutTrace, TraceMode, TraceSink, TraceStep};
+pub use trajectory::{
+    write_trajectory, Trajectory, TrajectoryConfig, TrajectoryFormat, TrajectoryStep,
+};
 pub use verify::{
-    resume_from_checkpoint, resume_from_checkpoint_with_config, verify_lychrel_resumable,
-    VerifyConfig, VerifyResult,
+    resume_from_checkpoint, resume_from_checkpoint_traced, resume_from_checkpoint_with_config,
+    verify_lychrel_resumable, verify_lychrel_traced, VerifyConfig, VerifyEngine, VerifyResult,
 };
@@ -0,0 +1,264 @@
+use crate::digit_number::DigitNumber;
+use num_bigint::BigUint;
+
+/// Decimal digits stored in each limb
+pub const LIMB_DIGITS: usize = 16;
+
+/// Value of one limb position (10^16)
+const LIMB_BASE: u64 = 10_000_000_000_000_000;
+
+/// Powers of ten up to the limb base
+const POW10: [u64; LIMB_DIGITS + 1] = {
+    let mut table = [1u64; LIMB_DIGITS + 1];
+    let mut i = 1;
+    while i <= LIMB_DIGITS {
+        table[i] = table[i - 1] * 10;
+        i += 1;
+    }
+    table
+};
+
+/// Digit reversal of every zero-padded 4-digit group
+static REV4: [u16; 10_000] = {
+    let mut table = [0u16; 10_000];
+    let mut i = 0;
+    while i < 10_000 {
+        let (a, b, c, d) = (i / 1000, (i / 100) % 10, (i / 10) % 10, i % 10);
+        table[i] = (d * 1000 + c * 100 + b * 10 + a) as u16;
+        i += 1;
+    }
+    table
+};
+
+/// Reverse all 16 digits of a limb, leading zeros included
+fn reverse_limb(x: u64) -> u64 {
+    let g0 = REV4[(x % 10_000) as usize] as u64;
+    let g1 = REV4[((x / 10_000) % 10_000) as usize] as u64;
+    let g2 = REV4[((x / 100_000_000) % 10_000) as usize] as u64;
+    let g3 = REV4[(x / 1_000_000_000_000) as usize] as u64;
+    ((g0 * 10_000 + g1) * 10_000 + g2) * 10_000 + g3
+}
+
+/// Limb `i` of the reverse, from the reversed limbs at `i` and `i + 1`
+///
+/// Reversing every limb reverses the number padded with `shift` leading
+/// zeros, so the true reverse is that value divided by `10^shift`, which
+/// pulls the low digits of the next limb down across the limb boundary.
+fn shifted(reversed: u64, next_reversed: u64, shift: usize) -> u64 {
+    if shift == 0 {
+        reversed
+    } else {
+        reversed / POW10[shift] + (next_reversed % POW10[shift]) * POW10[LIMB_DIGITS - shift]
+    }
+}
+
+/// A decimal number stored as little-endian base-10^16 limbs
+///
+/// Eight bytes hold sixteen digits, half the memory of `DigitNumber`, and
+/// the reverse-add step handles sixteen digits per addition. Reversal goes
+/// through a 4-digit lookup table and a cross-limb shift instead of
+/// touching individual digits.
+#[derive(Debug, Clone)]
+pub struct LimbNumber {
+    limbs: Vec<u64>,
+    digits: usize,
+    /// Original lower limbs during a step, kept to avoid reallocating
+    scratch: Vec<u64>,
+}
+
+impl LimbNumber {
+    /// Build a limb number from a BigUint
+    pub fn from_biguint(n: &BigUint) -> Self {
+        Self::from_digit_number(&DigitNumber::from_biguint(n))
+    }
+
+    /// Pack a decimal digit vector into limbs
+    ///
+    /// # Panics
+    ///
+    /// Panics if the digit vector is not in radix 10
+    pub fn from_digit_number(n: &DigitNumber) -> Self {
+        assert_eq!(n.radix(), 10, "limb numbers are decimal");
+        let limbs = n
+            .digits()
+            .chunks(LIMB_DIGITS)
+            .map(|chunk| chunk.iter().rev().fold(0u64, |acc, &d| acc * 10 + d as u64))
+            .collect();
+
+        LimbNumber {
+            limbs,
+            digits: n.len(),
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Unpack into a decimal digit vector
+    pub fn to_digit_number(&self) -> DigitNumber {
+        let mut digits = Vec::with_capacity(self.limbs.len() * LIMB_DIGITS);
+        for &limb in &self.limbs {
+            let mut limb = limb;
+            for _ in 0..LIMB_DIGITS {
+                digits.push((limb % 10) as u8);
+                limb /= 10;
+            }
+        }
+        digits.truncate(self.digits);
+        DigitNumber::from_digits_le(digits, 10)
+    }
+
+    /// Convert back into a BigUint
+    pub fn to_biguint(&self) -> BigUint {
+        self.to_digit_number().to_biguint()
+    }
+
+    /// Number of decimal digits
+    pub fn len(&self) -> usize {
+        self.digits
+    }
+
+    /// Check if the number has no digits (never true for a valid number)
+    pub fn is_empty(&self) -> bool {
+        self.digits == 0
+    }
+
+    /// Number of leading zero digits in the top limb
+    fn shift(&self) -> usize {
+        self.limbs.len() * LIMB_DIGITS - self.digits
+    }
+
+    /// Check if the number reads the same forwards and backwards
+    ///
+    /// Compares the lower half of the limbs against the matching limbs of the
+    /// reverse, stopping at the first difference.
+    pub fn is_palindrome(&self) -> bool {
+        let len = self.limbs.len();
+        let shift = self.shift();
+        let mut reversed = reverse_limb(self.limbs[len - 1]);
+
+        for i in 0..len.div_ceil(2) {
+            let next = if i + 1 < len {
+                reverse_limb(self.limbs[len - 2 - i])
+            } else {
+                0
+            };
+            if self.limbs[i] != shifted(reversed, next, shift) {
+                return false;
+            }
+            reversed = next;
+        }
+        true
+    }
+
+    /// Add the number to its reverse in place
+    ///
+    /// Limbs are updated from the least significant one upwards. The lower
+    /// half is copied first because the upper limbs of the reverse are made
+    /// from it after it has been overwritten.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the new value is a palindrome
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lychrel_finder::limb_number::LimbNumber;
+    /// use num_bigint::BigUint;
+    ///
+    /// let mut n = LimbNumber::from_biguint(&BigUint::from(196u32));
+    /// assert!(!n.reverse_add());
+    /// assert_eq!(n.to_biguint(), BigUint::from(887u32));
+    /// ```
+    pub fn reverse_add(&mut self) -> bool {
+        let len = self.limbs.len();
+        let shift = self.shift();
+        let saved = (len / 2 + 2).min(len);
+        self.scratch.clear();
+        self.scratch.extend_from_slice(&self.limbs[..saved]);
+
+        let original = |limbs: &[u64], scratch: &[u64], idx: usize| {
+            if idx < saved {
+                scratch[idx]
+            } else {
+                limbs[idx]
+            }
+        };
+
+        let mut reversed = reverse_limb(original(&self.limbs, &self.scratch, len - 1));
+        let mut carry = 0;
+        for i in 0..len {
+            let next = if i + 1 < len {
+                reverse_limb(original(&self.limbs, &self.scratch, len - 2 - i))
+            } else {
+                0
+            };
+            let v =
+                original(&self.limbs, &self.scratch, i) + shifted(reversed, next, shift) + carry;
+            if v >= LIMB_BASE {
+                self.limbs[i] = v - LIMB_BASE;
+                carry = 1;
+            } else {
+                self.limbs[i] = v;
+                carry = 0;
+            }
+            reversed = next;
+        }
+
+        if carry > 0 {
+            self.limbs.push(1);
+        }
+        let top = self.limbs[self.limbs.len() - 1];
+        self.digits = (self.limbs.len() - 1) * LIMB_DIGITS
+            + top.checked_ilog10().map_or(1, |l| l as usize + 1);
+
+        self.is_palindrome()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reverse_limb() {
+        assert_eq!(reverse_limb(0), 0);
+        assert_eq!(reverse_limb(1), 1_000_000_000_000_000);
+        assert_eq!(reverse_limb(1234567890123456), 6543210987654321);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for s in ["0", "7", "1234567890123456", "12345678901234567", "196"] {
+            let n: BigUint = s.parse().unwrap();
+            let limbs = LimbNumber::from_biguint(&n);
+            assert_eq!(limbs.to_biguint(), n);
+            assert_eq!(limbs.len(), s.len());
+        }
+    }
+
+    #[test]
+    fn test_matches_digit_engine() {
+        // 196 crosses many limb boundaries; 10^15 + 1 starts exactly one limb wide
+        for start in ["196", "89", "1000000000000001", "1186060307891929990"] {
+            let n: BigUint = start.parse().unwrap();
+            let mut limbs = LimbNumber::from_biguint(&n);
+            let mut digits = DigitNumber::from_biguint(&n);
+            for _ in 0..300 {
+                let palindrome = digits.reverse_add();
+                assert_eq!(limbs.reverse_add(), palindrome);
+                assert_eq!(limbs.to_digit_number(), digits);
+                if palindrome {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_palindrome_across_limbs() {
+        let n: BigUint = "123456789012345676543210987654321".parse().unwrap();
+        assert!(LimbNumber::from_biguint(&n).is_palindrome());
+        let n: BigUint = "123456789012345676543210987654320".parse().unwrap();
+        assert!(!LimbNumber::from_biguint(&n).is_palindrome());
+    }
+}
@@ -1,14 +1,20 @@
-use crate::thread_cache::{ThreadCache, ThreadInfo};
+use crate::adaptive_number::AdaptiveNumber;
+use crate::classification::Classification;
+use crate::digit_number::DigitNumber;
+use crate::thread_cache::{ThreadCache, ThreadInfo, THREAD_PREFIX};
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IterationResult {
     pub start_number: BigUint,
     pub is_palindrome: bool,
-    pub iterations: u32,
+    pub iterations: u64,
     pub final_number: Option<BigUint>,
     pub is_potential_lychrel: bool,
+    /// Palindrome start, or seed / kin once candidates have been classified
+    #[serde(default)]
+    pub classification: Option<Classification>,
 }
 
 /// Reverse the digits of a BigUint number
@@ -34,15 +40,31 @@ pub struct IterationResult {
 /// assert_eq!(reversed, BigUint::from(321u32));
 /// ```
 pub fn reverse_number(n: &BigUint) -> BigUint {
-    let digits = n.to_radix_le(10);
-    BigUint::from_radix_be(&digits, 10).unwrap()
+    reverse_number_radix(n, 10)
+}
+
+/// Reverse the digits of a BigUint number written in the given radix
+///
+/// # Examples
+///
+/// ```
+/// use lychrel_finder::lychrel::reverse_number_radix;
+/// use num_bigint::BigUint;
+///
+/// // 10110 in base 2 reverses to 01101
+/// let reversed = reverse_number_radix(&BigUint::from(22u32), 2);
+/// assert_eq!(reversed, BigUint::from(13u32));
+/// ```
+pub fn reverse_number_radix(n: &BigUint, radix: u32) -> BigUint {
+    let digits = n.to_radix_le(radix);
+    BigUint::from_radix_be(&digits, radix).unwrap()
 }
 
 /// Check if a number is a palindrome
 ///
 /// A palindrome reads the same forwards and backwards. This function
-/// converts the number to its string representation and checks if it
-/// reads the same in reverse.
+/// extracts the decimal digits of the number and compares them pairwise
+/// from both ends.
 ///
 /// # Arguments
 ///
@@ -62,8 +84,23 @@ pub fn reverse_number(n: &BigUint) -> BigUint {
 /// assert!(!is_palindrome(&BigUint::from(123u32)));
 /// ```
 pub fn is_palindrome(n: &BigUint) -> bool {
-    let reversed = reverse_number(n);
-    *n == reversed
+    is_palindrome_radix(n, 10)
+}
+
+/// Check if a number is a palindrome when written in the given radix
+///
+/// # Examples
+///
+/// ```
+/// use lychrel_finder::lychrel::is_palindrome_radix;
+/// use num_bigint::BigUint;
+///
+/// // 5 is 101 in base 2, but 12 is 1100
+/// assert!(is_palindrome_radix(&BigUint::from(5u32), 2));
+/// assert!(!is_palindrome_radix(&BigUint::from(12u32), 2));
+/// ```
+pub fn is_palindrome_radix(n: &BigUint, radix: u32) -> bool {
+    DigitNumber::from_biguint_radix(n, radix).is_palindrome()
 }
 
 /// Perform Lychrel iteration on a number
@@ -95,50 +132,82 @@ pub fn is_palindrome(n: &BigUint) -> bool {
 /// assert!(result.is_palindrome);
 /// assert_eq!(result.iterations, 24);
 /// ```
-pub fn lychrel_iteration(start: BigUint, max_iterations: u32) -> IterationResult {
-    let mut current = start.clone();
-    let mut iteration_count = 0;
-
-    if is_palindrome(&current) {
-        return IterationResult {
-            start_number: start,
-            is_palindrome: true,
-            iterations: 0,
-            final_number: Some(current),
-            is_potential_lychrel: false,
-        };
-    }
+pub fn lychrel_iteration(start: BigUint, max_iterations: u64) -> IterationResult {
+    lychrel_iteration_radix(start, max_iterations, 10)
+}
 
-    // Pre-calculate reversed number for the first iteration
-    let mut reversed = reverse_number(&current);
+/// Perform Lychrel iteration on a number in the given radix
+///
+/// Same algorithm as `lychrel_iteration`, but digits are reversed and
+/// palindromes detected in base `radix` (2 to 36).
+///
+/// # Examples
+///
+/// ```
+/// use lychrel_finder::lychrel::lychrel_iteration_radix;
+/// use num_bigint::BigUint;
+///
+/// // 10110 (22) is a proven Lychrel number in base 2
+/// let result = lychrel_iteration_radix(BigUint::from(22u32), 500, 2);
+/// assert!(result.is_potential_lychrel);
+/// ```
+pub fn lychrel_iteration_radix(start: BigUint, max_iterations: u64, radix: u32) -> IterationResult {
+    let mut current = AdaptiveNumber::from_biguint_radix(&start, radix);
+    iterate(&mut current, max_iterations).into_result(start, &current)
+}
 
-    while iteration_count < max_iterations {
-        current += reversed;
-        iteration_count += 1;
+/// Iteration count and outcome of a Lychrel iteration, without its numbers
+///
+/// Lets range searches count numbers they do not keep without building
+/// a BigUint for them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IterationOutcome {
+    pub iterations: u64,
+    pub is_palindrome: bool,
+}
 
-        // Calculate reverse of the new number
-        // This serves two purposes:
-        // 1. To check if the new number is a palindrome (by simple comparison)
-        // 2. To be ready for the next addition if the loop continues
-        reversed = reverse_number(&current);
+impl IterationOutcome {
+    /// Whether the start value was already a palindrome
+    pub fn is_palindrome_start(&self) -> bool {
+        self.is_palindrome && self.iterations == 0
+    }
 
-        if current == reversed {
+    /// Build the full result for a start value and the value its iteration ended on
+    pub fn into_result(self, start: BigUint, current: &AdaptiveNumber) -> IterationResult {
+        if self.is_palindrome_start() {
             return IterationResult {
+                final_number: Some(start.clone()),
                 start_number: start,
                 is_palindrome: true,
-                iterations: iteration_count,
-                final_number: Some(current),
+                iterations: 0,
                 is_potential_lychrel: false,
+                classification: Some(Classification::PalindromeStart),
             };
         }
+        IterationResult {
+            start_number: start,
+            is_palindrome: self.is_palindrome,
+            iterations: self.iterations,
+            final_number: Some(current.to_biguint()),
+            is_potential_lychrel: !self.is_palindrome,
+            classification: None,
+        }
     }
+}
 
-    IterationResult {
-        start_number: start,
-        is_palindrome: false,
-        iterations: iteration_count,
-        final_number: Some(current),
-        is_potential_lychrel: true,
+/// Reverse-add `current` in place until it is a palindrome or the limit is reached
+pub(crate) fn iterate(current: &mut AdaptiveNumber, max_iterations: u64) -> IterationOutcome {
+    let mut iterations = 0;
+    let mut is_palindrome = current.is_palindrome();
+
+    while !is_palindrome && iterations < max_iterations {
+        // Native words while the value fits, digit vector after promotion
+        is_palindrome = current.reverse_add();
+        iterations += 1;
+    }
+    IterationOutcome {
+        iterations,
+        is_palindrome,
     }
 }
 
@@ -153,6 +222,7 @@ pub fn lychrel_iteration(start: BigUint, max_iterations: u32) -> IterationResult
 /// * `start` - The starting number to test
 /// * `max_iterations` - The maximum number of iterations to perform
 /// * `cache` - A mutable reference to the thread cache for convergence detection
+/// * `external_path` - Buffer to reuse for the values a new thread is stored under
 ///
 /// # Returns
 ///
@@ -171,55 +241,54 @@ pub fn lychrel_iteration(start: BigUint, max_iterations: u32) -> IterationResult
 /// ```
 pub fn lychrel_iteration_with_cache(
     start: BigUint,
-    max_iterations: u32,
+    max_iterations: u64,
     cache: &mut ThreadCache,
     external_path: Option<&mut Vec<BigUint>>,
 ) -> IterationResult {
-    let mut current = start.clone();
-    let mut iteration_count = 0;
+    lychrel_iteration_with_cache_radix(start, max_iterations, 10, cache, external_path)
+}
 
-    // Manage path buffer: use external if provided, otherwise create local
-    let mut local_path = if external_path.is_none() {
-        Some(Vec::with_capacity(max_iterations as usize))
-    } else {
-        None
-    };
-
-    let path = match external_path {
-        Some(vec) => {
-            vec.clear();
-            vec
-        }
-        None => local_path.as_mut().unwrap(),
-    };
+/// Lychrel iteration with thread cache in the given radix
+///
+/// Same as `lychrel_iteration_with_cache`, but in base `radix`. A cache must
+/// only ever be used with a single radix, since threads differ between bases.
+pub fn lychrel_iteration_with_cache_radix(
+    start: BigUint,
+    max_iterations: u64,
+    radix: u32,
+    cache: &mut ThreadCache,
+    external_path: Option<&mut Vec<BigUint>>,
+) -> IterationResult {
+    let mut current = AdaptiveNumber::from_biguint_radix(&start, radix);
+    let mut iteration_count = 0;
 
     // If already palindrome
-    if is_palindrome(&current) {
+    if current.is_palindrome() {
         return IterationResult {
+            final_number: Some(start.clone()),
             start_number: start,
             is_palindrome: true,
             iterations: 0,
-            final_number: Some(current),
             is_potential_lychrel: false,
+            classification: Some(Classification::PalindromeStart),
         };
     }
 
-    // Pre-calculate reversed number for the first iteration
-    let mut reversed = reverse_number(&current);
-
     while iteration_count < max_iterations {
         // CHECK CACHE BEFORE ITERATION
-        if let Some(thread_info) = cache.check(&current) {
+        // The lookup works on the digit form, so no BigUint is built per step
+        if let Some(thread_info) = cache.check_number(&current) {
             // Found in cache! We know where this converges
-            let total_iterations = if thread_info.reached_palindrome {
-                iteration_count
-                    + thread_info
-                        .palindrome_at_iteration
-                        .unwrap_or(thread_info.max_iterations_tested)
+            let from_seed = if thread_info.reached_palindrome {
+                thread_info
+                    .palindrome_at_iteration
+                    .unwrap_or(thread_info.max_iterations_tested)
             } else {
                 // Still a potential Lychrel, but we've tested it before
-                iteration_count + thread_info.max_iterations_tested
+                thread_info.max_iterations_tested
             };
+            let total_iterations =
+                iteration_count + from_seed.saturating_sub(thread_info.iterations_from_seed);
 
             return IterationResult {
                 start_number: start,
@@ -227,37 +296,35 @@ pub fn lychrel_iteration_with_cache(
                 iterations: total_iterations,
                 final_number: None, // Don't compute final number for cached results
                 is_potential_lychrel: !thread_info.reached_palindrome,
+                classification: None,
             };
         }
 
-        // Normal iteration
-        current += reversed;
+        // Normal iteration on the adaptive engine
+        let palindrome = current.reverse_add();
         iteration_count += 1;
-        path.push(current.clone());
-
-        // Calculate reverse of the new number for palindrome check AND next iteration
-        reversed = reverse_number(&current);
 
-        if current == reversed {
+        if palindrome {
             // New thread with palindrome found!
             if cache.should_cache(iteration_count) {
                 let info = ThreadInfo {
                     seed_number: start.to_string(),
                     iterations_from_seed: 0,
                     max_iterations_tested: iteration_count,
-                    final_digits: current.to_string().len(),
+                    final_digits: current.len(),
                     reached_palindrome: true,
                     palindrome_at_iteration: Some(iteration_count),
                 };
-                cache.add_thread(path, info);
+                store_thread(cache, &start, radix, external_path, info);
             }
 
             return IterationResult {
                 start_number: start,
                 is_palindrome: true,
                 iterations: iteration_count,
-                final_number: Some(current),
+                final_number: Some(current.to_biguint()),
                 is_potential_lychrel: false,
+                classification: None,
             };
         }
     }
@@ -268,22 +335,52 @@ pub fn lychrel_iteration_with_cache(
             seed_number: start.to_string(),
             iterations_from_seed: 0,
             max_iterations_tested: iteration_count,
-            final_digits: current.to_string().len(),
+            final_digits: current.len(),
             reached_palindrome: false,
             palindrome_at_iteration: None,
         };
-        cache.add_thread(path, info);
+        store_thread(cache, &start, radix, external_path, info);
     }
 
     IterationResult {
         start_number: start,
         is_palindrome: false,
         iterations: iteration_count,
-        final_number: Some(current),
+        final_number: Some(current.to_biguint()),
         is_potential_lychrel: true,
+        classification: None,
     }
 }
 
+/// `start` followed by the value after each reverse-add step
+fn thread_values(start: &BigUint, radix: u32) -> impl Iterator<Item = BigUint> {
+    let mut current = AdaptiveNumber::from_biguint_radix(start, radix);
+    std::iter::once(start.clone()).chain(std::iter::from_fn(move || {
+        current.reverse_add();
+        Some(current.to_biguint())
+    }))
+}
+
+/// Store the thread of `start` under `start` and the values after its first steps
+///
+/// `start` is the seed, 0 iterations from itself. Only the values
+/// `add_thread` keeps are built, by taking those steps again, so numbers
+/// that are not stored never convert to BigUint.
+pub(crate) fn store_thread(
+    cache: &mut ThreadCache,
+    start: &BigUint,
+    radix: u32,
+    external_path: Option<&mut Vec<BigUint>>,
+    info: ThreadInfo,
+) {
+    let steps = info.max_iterations_tested.min(THREAD_PREFIX as u64) as usize;
+    let mut local_path = Vec::new();
+    let path = external_path.unwrap_or(&mut local_path);
+    path.clear();
+    path.extend(thread_values(start, radix).take(steps));
+    cache.add_thread(path, info);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -324,6 +421,23 @@ mod tests {
         assert_eq!(result.iterations, 100);
     }
 
+    #[test]
+    fn test_lychrel_iteration_radix() {
+        // Base 10 through the radix entry point matches the default
+        let result = lychrel_iteration_radix(BigUint::from(89u32), 100, 10);
+        assert_eq!(result.iterations, 24);
+
+        // 10110 (22) never reaches a palindrome in base 2
+        let result = lychrel_iteration_radix(BigUint::from(22u32), 1000, 2);
+        assert!(result.is_potential_lychrel);
+
+        // 110 (6) reaches 110 + 011 = 1001 in base 2 after one step
+        let result = lychrel_iteration_radix(BigUint::from(6u32), 100, 2);
+        assert!(result.is_palindrome);
+        assert_eq!(result.iterations, 1);
+        assert_eq!(result.final_number, Some(BigUint::from(9u32)));
+    }
+
     #[test]
     fn test_already_palindrome() {
         let result = lychrel_iteration(BigUint::from(121u32), 100);
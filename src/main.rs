@@ -1,14 +1,25 @@
 use clap::{Parser, Subcommand};
+use lychrel_finder::interrupt;
+use lychrel_finder::search_checkpoint::records_file;
 use lychrel_finder::{
-    lychrel_iteration, resume_from_checkpoint_with_config, search_range, search_range_resumable,
-    verify_lychrel_resumable, Checkpoint, GeneratorMode, HuntConfig, RecordHunter,
-    SearchCheckpoint, SearchConfig, SearchResults, VerifyConfig,
+    lychrel_iteration, lychrel_iteration_radix, parse_bound, parse_schedule, resume_delay_search,
+    resume_from_checkpoint_traced, search_delays, search_multi_pass, search_range,
+    search_range_resumable, search_range_streaming, search_source_resumable, verify_lychrel_traced,
+    write_trajectory, Checkpoint, Classification, DelayConfig, DelayTable, FilterStage,
+    GeneratorMode, HuntConfig, IterationResult, MultiPassConfig, NdjsonSink, NoResults,
+    NumberSource, RecordHuntCheckpoint, RecordHunter, SearchCheckpoint, SearchConfig,
+    SearchResults, SearchStatistics, StdoutTrace, StopConditions, StopReason, TraceMode,
+    TrajectoryConfig, TrajectoryFormat, VerifyConfig, VerifyEngine,
 };
 use num_bigint::BigUint;
+use num_traits::ToPrimitive;
 use std::fs::File;
 use std::io::Write;
 use std::time::Instant;
 
+/// Iteration limits of a multi-pass search, named so clap takes it as one value
+type Schedule = Vec<u64>;
+
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
@@ -28,19 +39,89 @@ enum Commands {
         number: String,
 
         #[arg(short, long, default_value = "10000")]
-        max_iterations: u32,
+        max_iterations: u64,
+
+        #[arg(
+            short,
+            long,
+            default_value = "10",
+            value_parser = clap::value_parser!(u32).range(2..=36),
+            help = "Number base (2-36) used to read, iterate and print numbers"
+        )]
+        base: u32,
+    },
+
+    #[command(about = "Export every value of the reverse-add sequence of a number")]
+    Trajectory {
+        #[arg(help = "The number to follow")]
+        number: String,
+
+        #[arg(short, long, default_value = "1000")]
+        max_iterations: u64,
+
+        #[arg(
+            short,
+            long,
+            default_value = "10",
+            value_parser = clap::value_parser!(u32).range(2..=36),
+            help = "Number base (2-36) used to read, iterate and print numbers"
+        )]
+        base: u32,
+
+        #[arg(
+            long,
+            default_value = "jsonl",
+            value_parser = ["jsonl", "csv"],
+            help = "Output format: JSON lines or CSV"
+        )]
+        format: String,
+
+        #[arg(short, long, help = "Output file (default: stdout)")]
+        output: Option<String>,
+
+        #[arg(
+            long,
+            default_value = "1000",
+            help = "Values with more digits are exported as digit count and hash only"
+        )]
+        max_value_digits: usize,
     },
 
     #[command(about = "Search for Lychrel numbers in a range")]
     Search {
-        #[arg(help = "Start of the range")]
-        start: u64,
+        #[arg(
+            value_parser = parse_bound,
+            required_unless_present = "source",
+            help = "Start of the range (decimal value of any size, or e.g. 1e25)"
+        )]
+        start: Option<BigUint>,
+
+        #[arg(
+            value_parser = parse_bound,
+            required_unless_present = "source",
+            help = "End of the range (decimal value of any size, or e.g. 2e25)"
+        )]
+        end: Option<BigUint>,
 
-        #[arg(help = "End of the range")]
-        end: u64,
+        #[arg(
+            long,
+            value_parser = NumberSource::parse,
+            conflicts_with_all = ["start", "end", "stream", "aggregate_only", "passes"],
+            help = "Search other numbers than a range: file:PATH, stdin, progression:START,STEP,COUNT, mask:1??9?0 or digits:N"
+        )]
+        source: Option<NumberSource>,
 
         #[arg(short, long, default_value = "10000")]
-        max_iterations: u32,
+        max_iterations: u64,
+
+        #[arg(
+            short,
+            long,
+            default_value = "10",
+            value_parser = clap::value_parser!(u32).range(2..=36),
+            help = "Number base (2-36) used to iterate and print numbers"
+        )]
+        base: u32,
 
         #[arg(short, long, help = "Output file for results (JSON)")]
         output: Option<String>,
@@ -48,10 +129,16 @@ enum Commands {
         #[arg(long, help = "Disable parallel processing")]
         no_parallel: bool,
 
+        #[arg(
+            long,
+            help = "Classify potential Lychrel numbers as seed or kin (keeps seed threads in memory)"
+        )]
+        classify: bool,
+
         #[arg(
             short = 'c',
             long,
-            help = "Save checkpoint every N numbers tested (default: 1000, use 0 to disable)"
+            help = "Save checkpoint every N numbers tested (default: 1000, or 100000 in parallel, use 0 to disable)"
         )]
         checkpoint_interval: Option<u64>,
 
@@ -67,6 +154,99 @@ enum Commands {
             help = "Force restart from beginning, ignoring existing checkpoint"
         )]
         force_restart: bool,
+
+        #[arg(
+            long,
+            help = "Stream every result as JSON lines to this file ('-' for stdout) instead of keeping them in memory"
+        )]
+        stream: Option<String>,
+
+        #[arg(
+            long,
+            help = "Keep only counts and the iteration histogram, not individual results"
+        )]
+        aggregate_only: bool,
+
+        #[arg(
+            long,
+            help = "Skip numbers that join an already tested thread, using a thread cache of this many entries"
+        )]
+        cache_size: Option<usize>,
+
+        #[arg(
+            long,
+            value_parser = parse_schedule,
+            help = "Test in passes of increasing iteration limits, e.g. 100,1000,10000 (replaces --max-iterations)"
+        )]
+        passes: Option<Schedule>,
+    },
+
+    #[command(about = "Find the smallest number needing each number of iterations (OEIS A023109)")]
+    Delays {
+        #[arg(
+            value_parser = parse_bound,
+            help = "Last number to search (decimal value of any size, or e.g. 1e9)"
+        )]
+        end: BigUint,
+
+        #[arg(
+            long,
+            value_parser = parse_bound,
+            default_value = "1",
+            help = "First number to search"
+        )]
+        start: BigUint,
+
+        #[arg(
+            short = 'k',
+            long,
+            default_value = "100",
+            help = "Largest iteration count to tabulate"
+        )]
+        max_delay: u64,
+
+        #[arg(
+            short,
+            long,
+            default_value = "10",
+            value_parser = clap::value_parser!(u32).range(2..=36),
+            help = "Number base (2-36) used to iterate and print numbers"
+        )]
+        base: u32,
+
+        #[arg(
+            short,
+            long,
+            help = "Write the table as 'k number' lines to this file as entries are found"
+        )]
+        table: Option<String>,
+
+        #[arg(short, long, help = "Output file for the finished table (JSON)")]
+        output: Option<String>,
+
+        #[arg(long, help = "Disable parallel processing")]
+        no_parallel: bool,
+
+        #[arg(
+            short = 'c',
+            long,
+            default_value = "100000",
+            help = "Save checkpoint every N numbers tested (use 0 to disable)"
+        )]
+        checkpoint_interval: u64,
+
+        #[arg(
+            short = 'f',
+            long,
+            help = "Checkpoint file path (default: delay_checkpoint_<start>_<end>_<k>.json)"
+        )]
+        checkpoint_file: Option<String>,
+
+        #[arg(
+            long,
+            help = "Force restart from beginning, ignoring existing checkpoint"
+        )]
+        force_restart: bool,
     },
 
     #[command(about = "Verify if a number is truly a Lychrel number with extensive testing")]
@@ -108,12 +288,39 @@ enum Commands {
             help = "Force restart from beginning, ignoring existing checkpoint"
         )]
         force_restart: bool,
+
+        #[arg(long, help = "Print every Nth reverse-add step with full numbers")]
+        trace_every: Option<u64>,
+
+        #[arg(long, help = "Print only the digit count every N iterations")]
+        trace_digits: Option<u64>,
+
+        #[arg(
+            long,
+            default_value = "10000",
+            help = "Switch to the base-10^16 limb engine above this many digits"
+        )]
+        limb_threshold: usize,
+
+        #[arg(
+            long,
+            default_value = "standard",
+            value_parser = ["standard", "pair-sum"],
+            help = "Iteration engine: digits/limbs, or pair sums with half the memory"
+        )]
+        engine: String,
     },
 
     #[command(about = "Resume verification from a checkpoint file")]
     Resume {
         #[arg(help = "Path to the checkpoint file")]
         checkpoint_file: String,
+
+        #[arg(long, help = "Print every Nth reverse-add step with full numbers")]
+        trace_every: Option<u64>,
+
+        #[arg(long, help = "Print only the digit count every N iterations")]
+        trace_digits: Option<u64>,
     },
 
     #[command(about = "Hunt for record-breaking Lychrel numbers with optimized thread detection")]
@@ -134,13 +341,13 @@ enum Commands {
         max_digits: Option<usize>,
 
         #[arg(long, help = "Target minimum iterations (overrides config file)")]
-        target_iterations: Option<u32>,
+        target_iterations: Option<u64>,
 
         #[arg(
             long,
             help = "Maximum iterations before considering it a Lychrel (overrides config file)"
         )]
-        max_iterations: Option<u32>,
+        max_iterations: Option<u64>,
 
         #[arg(long, help = "Target minimum final digits (overrides config file)")]
         target_final_digits: Option<usize>,
@@ -164,8 +371,71 @@ enum Commands {
         )]
         checkpoint_interval: Option<u64>,
 
+        #[arg(
+            long,
+            help = "Also checkpoint every N seconds, 0 to disable (overrides config file)"
+        )]
+        checkpoint_interval_secs: Option<u64>,
+
+        #[arg(
+            long,
+            help = "Print statistics every N numbers, 0 to disable (overrides config file)"
+        )]
+        stats_interval: Option<u64>,
+
+        #[arg(
+            long,
+            help = "Also print statistics every N seconds, 0 to disable (overrides config file)"
+        )]
+        stats_interval_secs: Option<u64>,
+
         #[arg(short = 'f', long, help = "Checkpoint file (overrides config file)")]
         checkpoint_file: Option<String>,
+
+        #[arg(
+            long,
+            value_parser = clap::value_parser!(u32).range(2..=36),
+            help = "Number base (2-36) to hunt in (overrides config file)"
+        )]
+        base: Option<u32>,
+
+        #[arg(
+            long,
+            help = "Force restart from the minimum digits, ignoring existing checkpoint"
+        )]
+        force_restart: bool,
+
+        #[arg(
+            long,
+            value_parser = parse_duration_secs,
+            help = "Stop after this long, e.g. 8h, 90m or 3600 (seconds), 0 to disable (overrides config file)"
+        )]
+        max_duration: Option<u64>,
+
+        #[arg(
+            long,
+            help = "Stop after testing N numbers in this run, 0 to disable (overrides config file)"
+        )]
+        max_numbers: Option<u64>,
+
+        #[arg(
+            long,
+            help = "Stop after finding N records in this run, 0 to disable (overrides config file)"
+        )]
+        max_records: Option<usize>,
+
+        #[arg(
+            long,
+            help = "Stop once a number reaches a palindrome after at least N iterations, 0 to disable (overrides config file)"
+        )]
+        stop_at_iterations: Option<u64>,
+
+        #[arg(
+            long,
+            value_parser = parse_bound,
+            help = "Stop when the generator reaches this number, before testing it (overrides config file)"
+        )]
+        stop_at: Option<BigUint>,
     },
 
     #[command(about = "Generate a default hunt configuration file")]
@@ -179,37 +449,58 @@ enum Commands {
 }
 
 struct SearchParams {
-    start: u64,
-    end: u64,
-    max_iterations: u32,
+    start: BigUint,
+    end: BigUint,
+    max_iterations: u64,
+    radix: u32,
     output_file: Option<String>,
     parallel: bool,
+    classify: bool,
     checkpoint_interval: Option<u64>,
     checkpoint_file: Option<String>,
     force_restart: bool,
+    stream: Option<String>,
+    aggregate_only: bool,
+    cache_size: Option<usize>,
+    passes: Option<Vec<u64>>,
+    source: Option<NumberSource>,
 }
 
 #[allow(clippy::too_many_arguments)]
 impl SearchParams {
     fn from_args(
-        start: u64,
-        end: u64,
-        max_iterations: u32,
+        start: BigUint,
+        end: BigUint,
+        max_iterations: u64,
+        radix: u32,
         output_file: Option<String>,
         parallel: bool,
+        classify: bool,
         checkpoint_interval: Option<u64>,
         checkpoint_file: Option<String>,
         force_restart: bool,
+        stream: Option<String>,
+        aggregate_only: bool,
+        cache_size: Option<usize>,
+        passes: Option<Vec<u64>>,
+        source: Option<NumberSource>,
     ) -> Self {
         Self {
             start,
             end,
             max_iterations,
+            radix,
             output_file,
             parallel,
+            classify,
             checkpoint_interval,
             checkpoint_file,
             force_restart,
+            stream,
+            aggregate_only,
+            cache_size,
+            passes,
+            source,
         }
     }
 }
@@ -218,14 +509,24 @@ struct HuntOverrides {
     config_file: Option<String>,
     min_digits: Option<usize>,
     max_digits: Option<usize>,
-    target_iterations: Option<u32>,
-    max_iterations: Option<u32>,
+    target_iterations: Option<u64>,
+    max_iterations: Option<u64>,
     target_final_digits: Option<usize>,
     cache_size: Option<usize>,
     warmup: Option<bool>,
     mode: Option<String>,
     checkpoint_interval: Option<u64>,
+    checkpoint_interval_secs: Option<u64>,
+    stats_interval: Option<u64>,
+    stats_interval_secs: Option<u64>,
     checkpoint_file: Option<String>,
+    radix: Option<u32>,
+    force_restart: bool,
+    max_duration: Option<u64>,
+    max_numbers: Option<u64>,
+    max_records: Option<usize>,
+    stop_at_iterations: Option<u64>,
+    stop_at: Option<BigUint>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -234,14 +535,24 @@ impl HuntOverrides {
         config_file: Option<String>,
         min_digits: Option<usize>,
         max_digits: Option<usize>,
-        target_iterations: Option<u32>,
-        max_iterations: Option<u32>,
+        target_iterations: Option<u64>,
+        max_iterations: Option<u64>,
         target_final_digits: Option<usize>,
         cache_size: Option<usize>,
         warmup: Option<bool>,
         mode: Option<String>,
         checkpoint_interval: Option<u64>,
+        checkpoint_interval_secs: Option<u64>,
+        stats_interval: Option<u64>,
+        stats_interval_secs: Option<u64>,
         checkpoint_file: Option<String>,
+        radix: Option<u32>,
+        force_restart: bool,
+        max_duration: Option<u64>,
+        max_numbers: Option<u64>,
+        max_records: Option<usize>,
+        stop_at_iterations: Option<u64>,
+        stop_at: Option<BigUint>,
     ) -> Self {
         Self {
             config_file,
@@ -254,7 +565,17 @@ impl HuntOverrides {
             warmup,
             mode,
             checkpoint_interval,
+            checkpoint_interval_secs,
+            stats_interval,
+            stats_interval_secs,
             checkpoint_file,
+            radix,
+            force_restart,
+            max_duration,
+            max_numbers,
+            max_records,
+            stop_at_iterations,
+            stop_at,
         }
     }
 }
@@ -266,31 +587,89 @@ fn main() {
         Commands::Test {
             number,
             max_iterations,
+            base,
         } => {
-            test_number(&number, max_iterations);
+            test_number(&number, max_iterations, base);
+        }
+        Commands::Trajectory {
+            number,
+            max_iterations,
+            base,
+            format,
+            output,
+            max_value_digits,
+        } => {
+            let config = TrajectoryConfig {
+                max_iterations,
+                radix: base,
+                max_value_digits,
+            };
+            export_trajectory(&number, config, &format, output.as_deref());
         }
         Commands::Search {
             start,
             end,
+            source,
             max_iterations,
+            base,
             output,
             no_parallel,
+            classify,
             checkpoint_interval,
             checkpoint_file,
             force_restart,
+            stream,
+            aggregate_only,
+            cache_size,
+            passes,
         } => {
+            // clap only lets the range bounds be missing when a source is given
             let params = SearchParams::from_args(
-                start,
-                end,
+                start.unwrap_or_default(),
+                end.unwrap_or_default(),
                 max_iterations,
+                base,
                 output,
                 !no_parallel,
+                classify,
                 checkpoint_interval,
                 checkpoint_file,
                 force_restart,
+                stream,
+                aggregate_only,
+                cache_size,
+                passes,
+                source,
             );
             search_numbers(params);
         }
+        Commands::Delays {
+            end,
+            start,
+            max_delay,
+            base,
+            table,
+            output,
+            no_parallel,
+            checkpoint_interval,
+            checkpoint_file,
+            force_restart,
+        } => {
+            let checkpoint_file = checkpoint_file.unwrap_or_else(|| {
+                format!("delay_checkpoint_{}_{}_{}.json", start, end, max_delay)
+            });
+            let config = DelayConfig {
+                start,
+                end,
+                max_delay,
+                radix: base,
+                parallel: !no_parallel,
+                checkpoint_interval: (checkpoint_interval > 0).then_some(checkpoint_interval),
+                checkpoint_file: Some(checkpoint_file),
+                table_file: table,
+            };
+            search_smallest_delays(config, output, force_restart);
+        }
         Commands::Verify {
             number,
             max_iterations,
@@ -298,6 +677,10 @@ fn main() {
             checkpoint_interval,
             checkpoint_file,
             force_restart,
+            trace_every,
+            trace_digits,
+            limb_threshold,
+            engine,
         } => {
             verify_number(
                 &number,
@@ -306,10 +689,20 @@ fn main() {
                 checkpoint_interval,
                 checkpoint_file,
                 force_restart,
+                trace_mode(trace_every, trace_digits),
+                limb_threshold,
+                match engine.as_str() {
+                    "pair-sum" => VerifyEngine::PairSum,
+                    _ => VerifyEngine::Standard,
+                },
             );
         }
-        Commands::Resume { checkpoint_file } => {
-            resume_verification(&checkpoint_file);
+        Commands::Resume {
+            checkpoint_file,
+            trace_every,
+            trace_digits,
+        } => {
+            resume_verification(&checkpoint_file, trace_mode(trace_every, trace_digits));
         }
         Commands::HuntRecord {
             config,
@@ -322,7 +715,17 @@ fn main() {
             warmup,
             mode,
             checkpoint_interval,
+            checkpoint_interval_secs,
+            stats_interval,
+            stats_interval_secs,
             checkpoint_file,
+            base,
+            force_restart,
+            max_duration,
+            max_numbers,
+            max_records,
+            stop_at_iterations,
+            stop_at,
         } => {
             let overrides = HuntOverrides::from_args(
                 config,
@@ -335,7 +738,17 @@ fn main() {
                 warmup,
                 mode,
                 checkpoint_interval,
+                checkpoint_interval_secs,
+                stats_interval,
+                stats_interval_secs,
                 checkpoint_file,
+                base,
+                force_restart,
+                max_duration,
+                max_numbers,
+                max_records,
+                stop_at_iterations,
+                stop_at,
             );
             hunt_records_from_config(overrides);
         }
@@ -348,21 +761,24 @@ fn main() {
     }
 }
 
-fn test_number(number_str: &str, max_iterations: u32) {
-    let number: BigUint = match number_str.parse() {
-        Ok(n) => n,
-        Err(_) => {
-            eprintln!("Error: Invalid number '{}'", number_str);
+fn test_number(number_str: &str, max_iterations: u64, radix: u32) {
+    let number = match BigUint::parse_bytes(number_str.as_bytes(), radix) {
+        Some(n) => n,
+        None => {
+            eprintln!("Error: Invalid base {} number '{}'", radix, number_str);
             std::process::exit(1);
         }
     };
 
-    println!("Testing number: {}", number);
+    println!("Testing number: {}", number.to_str_radix(radix));
+    if radix != 10 {
+        println!("Base: {} (decimal value {})", radix, number);
+    }
     println!("Max iterations: {}", max_iterations);
     println!();
 
     let start_time = Instant::now();
-    let result = lychrel_iteration(number.clone(), max_iterations);
+    let result = lychrel_iteration_radix(number.clone(), max_iterations, radix);
     let elapsed = start_time.elapsed();
 
     println!("Results:");
@@ -374,13 +790,13 @@ fn test_number(number_str: &str, max_iterations: u32) {
         } else {
             println!("  Status: Palindrome reached!");
             if let Some(final_num) = &result.final_number {
-                println!("  Final number: {}", final_num);
+                println!("  Final number: {}", final_num.to_str_radix(radix));
             }
         }
     } else {
         println!("  Status: POTENTIAL LYCHREL NUMBER");
         if let Some(final_num) = &result.final_number {
-            let final_str = final_num.to_string();
+            let final_str = final_num.to_str_radix(radix);
             if final_str.len() > 100 {
                 println!(
                     "  Final number: {}... ({} digits)",
@@ -388,7 +804,7 @@ fn test_number(number_str: &str, max_iterations: u32) {
                     final_str.len()
                 );
             } else {
-                println!("  Final number: {}", final_num);
+                println!("  Final number: {}", final_str);
             }
         }
     }
@@ -396,6 +812,78 @@ fn test_number(number_str: &str, max_iterations: u32) {
     println!("\nTime elapsed: {:.3}s", elapsed.as_secs_f64());
 }
 
+fn export_trajectory(
+    number_str: &str,
+    config: TrajectoryConfig,
+    format: &str,
+    output_file: Option<&str>,
+) {
+    let radix = config.radix;
+    let number = match BigUint::parse_bytes(number_str.as_bytes(), radix) {
+        Some(n) => n,
+        None => {
+            eprintln!("Error: Invalid base {} number '{}'", radix, number_str);
+            std::process::exit(1);
+        }
+    };
+    let format = if format == "csv" {
+        TrajectoryFormat::Csv
+    } else {
+        TrajectoryFormat::JsonLines
+    };
+
+    let written = match output_file {
+        Some(filename) => match File::create(filename) {
+            Ok(file) => {
+                write_trajectory(&number, config, format, &mut std::io::BufWriter::new(file))
+            }
+            Err(e) => {
+                eprintln!("Error creating file: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => write_trajectory(&number, config, format, &mut std::io::stdout().lock()),
+    };
+
+    match written {
+        Ok(Some(last)) => {
+            if let Some(filename) = output_file {
+                println!(
+                    "Trajectory of {} saved to: {}",
+                    number.to_str_radix(radix),
+                    filename
+                );
+                println!(
+                    "  Steps: {} ({} digits at the end{})",
+                    last.iteration,
+                    last.digits,
+                    if last.is_palindrome {
+                        ", palindrome"
+                    } else {
+                        ""
+                    }
+                );
+            }
+        }
+        Ok(None) => {}
+        // Output piped into a command that stopped reading early
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => {}
+        Err(e) => {
+            eprintln!("Error writing trajectory: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn trace_mode(trace_every: Option<u64>, trace_digits: Option<u64>) -> TraceMode {
+    match (trace_every, trace_digits) {
+        (Some(n), _) => TraceMode::Every(n),
+        (None, Some(n)) => TraceMode::DigitSummary(n),
+        (None, None) => TraceMode::None,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn verify_number(
     number_str: &str,
     max_iterations: u64,
@@ -403,6 +891,9 @@ fn verify_number(
     checkpoint_interval: Option<u64>,
     checkpoint_file: Option<String>,
     force_restart: bool,
+    trace: TraceMode,
+    limb_threshold: usize,
+    engine: VerifyEngine,
 ) {
     let number: BigUint = match number_str.parse() {
         Ok(n) => n,
@@ -463,7 +954,7 @@ fn verify_number(
 
             if input.is_empty() || input == "y" || input == "yes" {
                 println!("\nResuming from checkpoint...\n");
-                resume_verification(&checkpoint_file);
+                resume_verification(&checkpoint_file, trace);
                 return;
             } else {
                 println!("\nDeleting old checkpoint and starting fresh...\n");
@@ -485,6 +976,9 @@ fn verify_number(
     println!("Number to verify: {}", number);
     println!("Max iterations: {}", max_iterations);
     println!("Progress interval: every {} iterations", progress_interval);
+    if engine == VerifyEngine::PairSum {
+        println!("Engine: pair sums");
+    }
     if let Some(interval) = checkpoint_interval {
         println!("Checkpoint interval: every {} iterations", interval);
         println!("Checkpoint file: {}", checkpoint_file);
@@ -499,34 +993,42 @@ fn verify_number(
         progress_interval,
         checkpoint_interval,
         checkpoint_file: Some(checkpoint_file.clone()),
+        limb_threshold,
+        engine,
     };
 
-    let result = verify_lychrel_resumable(config, |iteration, current, elapsed, is_checkpoint| {
-        let digit_count = current.to_string().len();
-        let speed = if elapsed.as_secs_f64() > 0.0 {
-            iteration as f64 / elapsed.as_secs_f64()
-        } else {
-            0.0
-        };
+    install_interrupt_handler();
+    let mut trace_sink = StdoutTrace::new(trace);
+    let result = verify_lychrel_traced(
+        config,
+        &mut trace_sink,
+        |iteration, digit_count, elapsed, is_checkpoint| {
+            let speed = if elapsed.as_secs_f64() > 0.0 {
+                iteration as f64 / elapsed.as_secs_f64()
+            } else {
+                0.0
+            };
 
-        if is_checkpoint {
-            println!(
+            if is_checkpoint {
+                println!(
                 "[Progress] Iteration: {:<12} | Digits: {:<8} | Time: {:<8.2}s | Speed: {:.0} iter/s | ✓ Checkpoint saved",
                 iteration,
                 digit_count,
                 elapsed.as_secs_f64(),
                 speed
             );
-        } else {
-            println!(
+            } else {
+                println!(
                 "[Progress] Iteration: {:<12} | Digits: {:<8} | Time: {:<8.2}s | Speed: {:.0} iter/s",
                 iteration,
                 digit_count,
                 elapsed.as_secs_f64(),
                 speed
             );
-        }
-    });
+            }
+        },
+    );
+    exit_if_interrupted(&checkpoint_file);
 
     println!("\n========================================");
     println!("  VERIFICATION COMPLETE");
@@ -572,7 +1074,7 @@ fn verify_number(
     println!("========================================");
 }
 
-fn resume_verification(checkpoint_file: &str) {
+fn resume_verification(checkpoint_file: &str, trace: TraceMode) {
     println!("========================================");
     println!("  RESUME FROM CHECKPOINT");
     println!("========================================");
@@ -588,16 +1090,17 @@ fn resume_verification(checkpoint_file: &str) {
 
     println!("Checkpoint information:");
     println!("  Start number: {}", checkpoint.start_number);
-    println!(
-        "  Current number: {} ({} digits)",
-        checkpoint
-            .current_number
-            .to_string()
-            .chars()
-            .take(50)
-            .collect::<String>(),
-        checkpoint.digit_count()
-    );
+    match checkpoint.current_number {
+        Some(ref current) => println!(
+            "  Current number: {} ({} digits)",
+            current.to_string().chars().take(50).collect::<String>(),
+            checkpoint.digit_count()
+        ),
+        None => println!(
+            "  Current number: stored as pair sums ({} digits)",
+            checkpoint.digit_count()
+        ),
+    }
     println!(
         "  Iterations completed: {}",
         checkpoint.iterations_completed
@@ -607,6 +1110,9 @@ fn resume_verification(checkpoint_file: &str) {
         checkpoint.iterations_remaining()
     );
     println!("  Progress: {:.2}%", checkpoint.progress_percentage());
+    if checkpoint.pair_sums.is_some() {
+        println!("  Engine: pair sums");
+    }
     println!("  Elapsed time: {:.3}s", checkpoint.elapsed_secs);
     println!("  Saved at: {}", checkpoint.timestamp);
 
@@ -619,13 +1125,14 @@ fn resume_verification(checkpoint_file: &str) {
     }
     println!("========================================\n");
 
-    let checkpoint_file_owned = checkpoint_file.to_string();
-    let result = resume_from_checkpoint_with_config(
+    install_interrupt_handler();
+    let mut trace_sink = StdoutTrace::new(trace);
+    let result = resume_from_checkpoint_traced(
         checkpoint,
-        checkpoint_file_owned.clone(),
-        checkpoint_interval,
-        |iteration, current: &BigUint, elapsed: std::time::Duration, is_checkpoint: bool| {
-            let digit_count = current.to_string().len();
+        Some(checkpoint_file.to_string()),
+        Some(checkpoint_interval),
+        &mut trace_sink,
+        |iteration, digit_count: usize, elapsed: std::time::Duration, is_checkpoint: bool| {
             let speed = if elapsed.as_secs_f64() > 0.0 {
                 iteration as f64 / elapsed.as_secs_f64()
             } else {
@@ -651,6 +1158,7 @@ fn resume_verification(checkpoint_file: &str) {
             }
         },
     );
+    exit_if_interrupted(checkpoint_file);
 
     println!("\n========================================");
     println!("  VERIFICATION COMPLETE");
@@ -697,37 +1205,74 @@ fn resume_verification(checkpoint_file: &str) {
 }
 
 fn search_numbers(params: SearchParams) {
-    let checkpoint_file = params
-        .checkpoint_file
-        .unwrap_or_else(|| format!("search_checkpoint_{}_{}.json", params.start, params.end));
+    if params.source.is_none() && params.start > params.end {
+        eprintln!(
+            "Error: Start of the range ({}) is greater than its end ({})",
+            params.start, params.end
+        );
+        std::process::exit(1);
+    }
+
+    if params.passes.is_some() {
+        multi_pass_search(params);
+        return;
+    }
 
+    if params.stream.is_some() || params.aggregate_only {
+        stream_search(params);
+        return;
+    }
+
+    let checkpoint_file = params
+        .checkpoint_file
+        .unwrap_or_else(|| match params.source {
+            Some(ref source) => format!("search_checkpoint_{}.json", file_name_part(source)),
+            None => format!("search_checkpoint_{}_{}.json", params.start, params.end),
+        });
+
+    // Numbers from stdin cannot be read a second time, and the resume
+    // prompt would read from stdin as well
+    let from_stdin = params.source == Some(NumberSource::Stdin);
     let checkpoint_interval = match params.checkpoint_interval {
+        _ if from_stdin => None,
         Some(0) => None,
         Some(n) => Some(n),
+        // Parallel runs go through numbers much faster, so save less often
+        None if params.parallel => Some(100_000),
         None => Some(1000),
     };
 
-    if !params.force_restart && !params.parallel {
+    if !params.force_restart && !from_stdin {
         if let Ok(existing_checkpoint) = SearchCheckpoint::load(&checkpoint_file) {
             println!("========================================");
             println!("  SEARCH CHECKPOINT FOUND!");
             println!("========================================");
-            println!(
-                "Search range: {} to {}",
-                existing_checkpoint.start_range, existing_checkpoint.end_range
-            );
-            println!(
-                "  Progress: {:.2}%",
-                existing_checkpoint.progress_percentage()
-            );
+            match existing_checkpoint.source {
+                Some(ref source) => println!("Search over: {}", source),
+                None => println!(
+                    "Search range: {} to {}",
+                    existing_checkpoint.start_range, existing_checkpoint.end_range
+                ),
+            }
+            if existing_checkpoint.numbers_remaining().is_some() {
+                println!(
+                    "  Progress: {:.2}%",
+                    existing_checkpoint.progress_percentage()
+                );
+            }
+            if existing_checkpoint.is_parallel() {
+                println!("  Mode: parallel");
+            }
+            if let Some(size) = existing_checkpoint.cache_size {
+                println!("  Thread cache: {} entries", size);
+            }
             println!("  Numbers tested: {}", existing_checkpoint.numbers_tested);
-            println!(
-                "  Numbers remaining: {}",
-                existing_checkpoint.numbers_remaining()
-            );
+            if let Some(remaining) = existing_checkpoint.numbers_remaining() {
+                println!("  Numbers remaining: {}", remaining);
+            }
             println!(
                 "  Potential Lychrel found so far: {}",
-                existing_checkpoint.potential_lychrel_found.len()
+                existing_checkpoint.potential_lychrel_count()
             );
             println!("  Elapsed time: {:.3}s", existing_checkpoint.elapsed_secs);
             println!("  Saved at: {}", existing_checkpoint.timestamp);
@@ -756,27 +1301,22 @@ fn search_numbers(params: SearchParams) {
                 return;
             } else {
                 println!("\nDeleting old checkpoint and starting fresh...\n");
-                if let Err(e) = std::fs::remove_file(&checkpoint_file) {
-                    eprintln!("Warning: Could not delete checkpoint file: {}", e);
-                }
+                delete_search_checkpoint(&checkpoint_file);
             }
         }
-    } else if !params.parallel
-        && std::path::Path::new(&checkpoint_file).exists()
-        && params.force_restart
-    {
+    } else if !from_stdin && std::path::Path::new(&checkpoint_file).exists() {
         println!("Deleting existing checkpoint (--force-restart)...\n");
-        if let Err(e) = std::fs::remove_file(&checkpoint_file) {
-            eprintln!("Warning: Could not delete checkpoint file: {}", e);
-        }
+        delete_search_checkpoint(&checkpoint_file);
     }
 
-    if params.parallel && checkpoint_interval.is_some() {
-        println!("Warning: Checkpoints are not supported with parallel processing. Disabling checkpoints.\n");
+    match params.source {
+        Some(ref source) => println!("Searching: {}", source),
+        None => println!("Searching range: {} to {}", params.start, params.end),
     }
-
-    println!("Searching range: {} to {}", params.start, params.end);
     println!("Max iterations: {}", params.max_iterations);
+    if params.radix != 10 {
+        println!("Base: {}", params.radix);
+    }
     println!(
         "Parallel processing: {}",
         if params.parallel {
@@ -785,76 +1325,434 @@ fn search_numbers(params: SearchParams) {
             "disabled"
         }
     );
-    if !params.parallel {
-        if let Some(interval) = checkpoint_interval {
-            println!("Checkpoint interval: every {} numbers", interval);
-            println!("Checkpoint file: {}", checkpoint_file);
+    if let Some(size) = params.cache_size {
+        println!("Thread cache: {} entries", size);
+    }
+    if let Some(interval) = checkpoint_interval {
+        println!("Checkpoint interval: every {} numbers", interval);
+        println!("Checkpoint file: {}", checkpoint_file);
+    } else if from_stdin {
+        println!("Checkpoint saving: disabled (numbers from stdin)");
+    } else {
+        println!("Checkpoint saving: disabled");
+    }
+    println!();
+
+    let start_time = Instant::now();
+    let config = SearchConfig {
+        start: params.start.clone(),
+        end: params.end.clone(),
+        max_iterations: params.max_iterations,
+        radix: params.radix,
+        classify: params.classify,
+        parallel: params.parallel,
+        checkpoint_interval,
+        checkpoint_file: (!from_stdin).then(|| checkpoint_file.clone()),
+        cache_size: params.cache_size,
+    };
+    if !from_stdin {
+        install_interrupt_handler();
+    }
+
+    let total_numbers = match params.source {
+        Some(ref source) => source.count(params.radix),
+        None => Some(&params.end - &params.start + 1u32),
+    };
+    let mut last_display = 0u64;
+    let display_interval = 100;
+
+    let progress = |tested: u64, current: &BigUint, is_checkpoint: bool| {
+        if is_checkpoint || tested - last_display >= display_interval {
+            print_search_progress(tested, total_numbers.as_ref(), current, is_checkpoint);
+            last_display = tested;
+        }
+    };
+    let results = match params.source {
+        Some(ref source) => match search_source_resumable(source, &config, progress) {
+            Ok(results) => results,
+            Err(e) => {
+                eprintln!("Error: Failed to read {}: {}", source, e);
+                std::process::exit(1);
+            }
+        },
+        None => search_range_resumable(config, progress),
+    };
+    exit_if_interrupted(&checkpoint_file);
+
+    let elapsed = start_time.elapsed();
+
+    print_search_results(&results, elapsed, params.radix);
+
+    if let Some(filename) = params.output_file {
+        save_results_to_file(&results, &filename);
+    }
+
+    // Clean up checkpoint file on successful completion
+    if std::path::Path::new(&checkpoint_file).exists() {
+        delete_search_checkpoint(&checkpoint_file);
+    }
+}
+
+fn stream_search(params: SearchParams) {
+    // With results going to stdout, the report goes to stderr
+    let to_stdout = params.stream.as_deref() == Some("-");
+    let report = |line: String| {
+        if to_stdout {
+            eprintln!("{}", line);
         } else {
-            println!("Checkpoint saving: disabled");
+            println!("{}", line);
         }
+    };
+
+    report(format!(
+        "Searching range: {} to {}",
+        params.start, params.end
+    ));
+    report(format!("Max iterations: {}", params.max_iterations));
+    if params.radix != 10 {
+        report(format!("Base: {}", params.radix));
     }
-    println!();
+    match params.stream {
+        Some(ref path) if path != "-" => report(format!("Streaming results to: {}", path)),
+        Some(_) => {}
+        None => report("Aggregate only: individual results are not kept".to_string()),
+    }
+    if let Some(size) = params.cache_size {
+        report(format!("Thread cache: {} entries", size));
+    }
+    if params.classify {
+        report("Note: --classify is not applied to streamed results".to_string());
+    }
+    report("Checkpoint saving: disabled (streaming)\n".to_string());
+
+    let config = SearchConfig {
+        start: params.start.clone(),
+        end: params.end.clone(),
+        max_iterations: params.max_iterations,
+        radix: params.radix,
+        classify: false,
+        parallel: params.parallel,
+        checkpoint_interval: None,
+        checkpoint_file: None,
+        cache_size: params.cache_size,
+    };
 
     let start_time = Instant::now();
-    let results = if params.parallel {
-        let config = SearchConfig {
-            start: BigUint::from(params.start),
-            end: BigUint::from(params.end),
-            max_iterations: params.max_iterations,
-            parallel: true,
-            checkpoint_interval: None,
-            checkpoint_file: None,
-        };
-        search_range(config)
-    } else {
-        let config = SearchConfig {
-            start: BigUint::from(params.start),
-            end: BigUint::from(params.end),
-            max_iterations: params.max_iterations,
-            parallel: false,
-            checkpoint_interval,
-            checkpoint_file: Some(checkpoint_file.clone()),
-        };
+    let summary = match params.stream {
+        Some(ref path) => {
+            let writer: Box<dyn Write> = if path == "-" {
+                Box::new(std::io::stdout().lock())
+            } else {
+                match File::create(path) {
+                    Ok(file) => Box::new(std::io::BufWriter::new(file)),
+                    Err(e) => {
+                        eprintln!("Error creating file: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            };
+            let mut sink = NdjsonSink::new(writer);
+            let summary = search_range_streaming(config, &mut sink);
+            if let Err(e) = sink.finish() {
+                eprintln!("Error writing results: {}", e);
+                std::process::exit(1);
+            }
+            summary
+        }
+        None => search_range_streaming(config, &mut NoResults),
+    };
+    let elapsed = start_time.elapsed();
+
+    report("Search complete!".to_string());
+    report(format!("  Total tested: {}", summary.total_tested));
+    report(format!(
+        "  Potential Lychrel numbers found: {}",
+        summary.potential_lychrel
+    ));
+    report(format!(
+        "  Numbers reaching palindromes: {}",
+        summary.palindromes_found
+    ));
+    report(format!(
+        "  Numbers already palindromes: {}",
+        summary.palindrome_starts
+    ));
+    if let Some(rate) = summary.cache_hit_rate() {
+        report(format!("  Cache hit rate: {:.1}%", rate * 100.0));
+    }
+    report(format!("  Time elapsed: {:.3}s", elapsed.as_secs_f64()));
+
+    for line in statistics_lines(&summary.statistics, params.radix) {
+        report(line);
+    }
+
+    if let Some(filename) = params.output_file {
+        match serde_json::to_string_pretty(&summary) {
+            Ok(json) => match std::fs::write(&filename, json) {
+                Ok(()) => report(format!("\nSummary saved to: {}", filename)),
+                Err(e) => eprintln!("Error writing to file: {}", e),
+            },
+            Err(e) => eprintln!("Error serializing summary: {}", e),
+        }
+    }
+}
+
+fn multi_pass_search(params: SearchParams) {
+    let schedule = params.passes.unwrap_or_default();
+
+    println!("Searching range: {} to {}", params.start, params.end);
+    println!(
+        "Iteration passes: {}",
+        schedule
+            .iter()
+            .map(|limit| limit.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if params.radix != 10 {
+        println!("Base: {}", params.radix);
+    }
+    if params.stream.is_some() || params.aggregate_only {
+        println!("Note: --stream and --aggregate-only are not used with --passes");
+    }
+    println!("Checkpoint saving: disabled (multi-pass)\n");
 
-        let total_numbers = params.end - params.start + 1;
-        let mut last_display = 0u64;
-        let display_interval = 100;
+    let start_time = Instant::now();
+    let results = search_multi_pass(MultiPassConfig {
+        start: params.start,
+        end: params.end,
+        schedule,
+        radix: params.radix,
+        parallel: params.parallel,
+        cache_size: params.cache_size,
+    });
+    let elapsed = start_time.elapsed();
 
-        search_range_resumable(config, |tested, current, is_checkpoint| {
-            if is_checkpoint || tested - last_display >= display_interval {
-                let progress = (tested as f64 / total_numbers as f64) * 100.0;
+    println!("Search complete!");
+    println!("  Total tested: {}", results.total_tested);
+    println!(
+        "  Numbers already palindromes: {}",
+        results.palindrome_starts
+    );
+    println!("  Time elapsed: {:.3}s", elapsed.as_secs_f64());
+    println!();
+    println!("Passes:");
+    println!(
+        "  {:>12}  {:>12}  {:>12}  {:>12}",
+        "iterations", "tested", "dropped", "survivors"
+    );
+    for pass in &results.passes {
+        println!(
+            "  {:>12}  {:>12}  {:>12}  {:>12}",
+            pass.max_iterations,
+            pass.tested,
+            pass.dropped.len(),
+            pass.survivors
+        );
+    }
+
+    for line in statistics_lines(&results.statistics, params.radix) {
+        println!("{}", line);
+    }
+
+    if !results.potential_lychrel.is_empty() {
+        println!("\nPotential Lychrel numbers:");
+        for result in &results.potential_lychrel {
+            println!("  - {}", result.start_number.to_str_radix(params.radix));
+        }
+    }
+
+    if let Some(filename) = params.output_file {
+        match serde_json::to_string_pretty(&results) {
+            Ok(json) => match std::fs::write(&filename, json) {
+                Ok(()) => println!("\nResults saved to: {}", filename),
+                Err(e) => eprintln!("Error writing to file: {}", e),
+            },
+            Err(e) => eprintln!("Error serializing results: {}", e),
+        }
+    }
+}
+
+fn search_smallest_delays(config: DelayConfig, output_file: Option<String>, force_restart: bool) {
+    if config.start > config.end {
+        eprintln!(
+            "Error: Start of the range ({}) is greater than its end ({})",
+            config.start, config.end
+        );
+        std::process::exit(1);
+    }
+    let checkpoint_file = config.checkpoint_file.clone().unwrap_or_default();
+
+    let mut resume_from = None;
+    if force_restart {
+        if std::path::Path::new(&checkpoint_file).exists() {
+            println!("Deleting existing checkpoint (--force-restart)...\n");
+            if let Err(e) = std::fs::remove_file(&checkpoint_file) {
+                eprintln!("Warning: Could not delete checkpoint file: {}", e);
+            }
+        }
+    } else if let Ok(existing) = DelayTable::load(&checkpoint_file) {
+        if existing.matches(&config) {
+            println!("========================================");
+            println!("  DELAY CHECKPOINT FOUND!");
+            println!("========================================");
+            println!("  Searched up to: {}", &existing.next - 1u32);
+            println!("  Numbers tested: {}", existing.numbers_tested);
+            println!(
+                "  Delays found: {}/{}",
+                existing.smallest.len(),
+                existing.max_delay
+            );
+            println!("  Elapsed time: {:.3}s", existing.elapsed_secs);
+            println!("  Saved at: {}", existing.timestamp);
+            println!("========================================");
+            println!("\nDo you want to resume from this checkpoint?");
+            println!("  [Y] Resume from checkpoint (default)");
+            println!("  [N] Start fresh (delete checkpoint)");
+            print!("\nYour choice (Y/n): ");
+            std::io::Write::flush(&mut std::io::stdout()).unwrap();
+
+            let mut input = String::new();
+            std::io::stdin().read_line(&mut input).unwrap();
+            let input = input.trim().to_lowercase();
+
+            if input.is_empty() || input == "y" || input == "yes" {
+                println!("\nResuming search from checkpoint...\n");
+                resume_from = Some(existing);
+            } else {
+                println!("\nDeleting old checkpoint and starting fresh...\n");
+                if let Err(e) = std::fs::remove_file(&checkpoint_file) {
+                    eprintln!("Warning: Could not delete checkpoint file: {}", e);
+                }
+            }
+        } else {
+            println!(
+                "Ignoring {}: it belongs to another range, limit or base\n",
+                checkpoint_file
+            );
+        }
+    }
+
+    println!(
+        "Smallest number for each delay from 1 to {}",
+        config.max_delay
+    );
+    println!("Searching range: {} to {}", config.start, config.end);
+    if config.radix != 10 {
+        println!("Base: {}", config.radix);
+    }
+    if let Some(ref table) = config.table_file {
+        println!("Table file: {}", table);
+    }
+    if let Some(interval) = config.checkpoint_interval {
+        println!("Checkpoint interval: every {} numbers", interval);
+        println!("Checkpoint file: {}", checkpoint_file);
+    } else {
+        println!("Checkpoint saving: disabled");
+    }
+    println!();
+
+    let radix = config.radix;
+    let total = &config.end - &config.start + 1u32;
+    let mut last_display = 0u64;
+    let display_interval = 100_000;
+    let progress = |table: &DelayTable, is_checkpoint: bool| {
+        if is_checkpoint || table.numbers_tested - last_display >= display_interval {
+            println!(
+                "[Delays] Tested: {} | Current: {} | Found: {}/{}{}",
+                table.numbers_tested,
+                &table.next - 1u32,
+                table.smallest.len(),
+                table.max_delay,
                 if is_checkpoint {
-                    println!(
-                        "[Search] Tested: {}/{} ({:.1}%) | Current: {} | ✓ Checkpoint saved",
-                        tested, total_numbers, progress, current
-                    );
+                    " | ✓ Checkpoint saved"
                 } else {
-                    println!(
-                        "[Search] Tested: {}/{} ({:.1}%) | Current: {}",
-                        tested, total_numbers, progress, current
-                    );
+                    ""
                 }
-                last_display = tested;
-            }
-        })
+            );
+            last_display = table.numbers_tested;
+        }
     };
 
+    install_interrupt_handler();
+    let start_time = Instant::now();
+    let outcome = match resume_from {
+        Some(table) => resume_delay_search(&config, table, progress),
+        None => search_delays(&config, progress),
+    };
+    let table = match outcome {
+        Ok(table) => table,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    exit_if_interrupted(&checkpoint_file);
     let elapsed = start_time.elapsed();
 
-    print_search_results(&results, elapsed);
+    println!("Search complete!");
+    println!("  Total tested: {} of {}", table.numbers_tested, total);
+    println!("  Time elapsed: {:.3}s", elapsed.as_secs_f64());
+    println!();
+    println!("Smallest number for each delay:");
+    println!("  {:>6}  number", "delay");
+    for (k, number) in &table.smallest {
+        println!("  {:>6}  {}", k, number.to_str_radix(radix));
+    }
+    let missing = table.missing();
+    if !missing.is_empty() {
+        println!(
+            "  No number in range for {} delays, e.g. {}",
+            missing.len(),
+            missing
+                .iter()
+                .take(10)
+                .map(|k| k.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    println!();
+    println!("Delay records:");
+    for record in &table.records {
+        println!(
+            "  {:>6}  {}",
+            record.delay,
+            record.number.to_str_radix(radix)
+        );
+    }
 
-    if let Some(filename) = params.output_file {
-        save_results_to_file(&results, &filename);
+    if let Some(filename) = output_file {
+        match serde_json::to_string_pretty(&table) {
+            Ok(json) => match std::fs::write(&filename, json) {
+                Ok(()) => println!("\nResults saved to: {}", filename),
+                Err(e) => eprintln!("Error writing to file: {}", e),
+            },
+            Err(e) => eprintln!("Error serializing results: {}", e),
+        }
     }
 
     // Clean up checkpoint file on successful completion
-    if !params.parallel && std::path::Path::new(&checkpoint_file).exists() {
+    if std::path::Path::new(&checkpoint_file).exists() {
         if let Err(e) = std::fs::remove_file(&checkpoint_file) {
             eprintln!("Warning: Could not delete checkpoint file: {}", e);
         }
     }
 }
 
+/// Delete a search checkpoint along with its records file
+fn delete_search_checkpoint(checkpoint_file: &str) {
+    if let Err(e) = std::fs::remove_file(checkpoint_file) {
+        eprintln!("Warning: Could not delete checkpoint file: {}", e);
+    }
+    let records = records_file(checkpoint_file);
+    if std::path::Path::new(&records).exists() {
+        if let Err(e) = std::fs::remove_file(&records) {
+            eprintln!("Warning: Could not delete records file: {}", e);
+        }
+    }
+}
+
 fn resume_search(checkpoint_file: &str, output_file: Option<String>) {
     use lychrel_finder::resume_search_from_checkpoint;
 
@@ -866,40 +1764,31 @@ fn resume_search(checkpoint_file: &str, output_file: Option<String>) {
         }
     };
 
-    let total_numbers = if let (Ok(start), Ok(end)) = (
-        checkpoint.start_range.to_string().parse::<u64>(),
-        checkpoint.end_range.to_string().parse::<u64>(),
-    ) {
-        end - start + 1
-    } else {
-        0
+    let total_numbers = match checkpoint.source {
+        Some(ref source) => source.count(checkpoint.radix),
+        None if checkpoint.end_range >= checkpoint.start_range => {
+            Some(&checkpoint.end_range - &checkpoint.start_range + 1u32)
+        }
+        None => Some(BigUint::from(0u32)),
     };
 
     let mut last_display = checkpoint.numbers_tested;
     let display_interval = 100;
+    let radix = checkpoint.radix;
 
+    install_interrupt_handler();
     let start_time = Instant::now();
     let results = resume_search_from_checkpoint(checkpoint, |tested, current, is_checkpoint| {
         if is_checkpoint || tested - last_display >= display_interval {
-            let progress = (tested as f64 / total_numbers as f64) * 100.0;
-            if is_checkpoint {
-                println!(
-                    "[Search] Tested: {}/{} ({:.1}%) | Current: {} | ✓ Checkpoint saved",
-                    tested, total_numbers, progress, current
-                );
-            } else {
-                println!(
-                    "[Search] Tested: {}/{} ({:.1}%) | Current: {}",
-                    tested, total_numbers, progress, current
-                );
-            }
+            print_search_progress(tested, total_numbers.as_ref(), current, is_checkpoint);
             last_display = tested;
         }
     });
+    exit_if_interrupted(checkpoint_file);
 
     let elapsed = start_time.elapsed();
 
-    print_search_results(&results, elapsed);
+    print_search_results(&results, elapsed, radix);
 
     if let Some(filename) = output_file {
         save_results_to_file(&results, &filename);
@@ -907,13 +1796,45 @@ fn resume_search(checkpoint_file: &str, output_file: Option<String>) {
 
     // Clean up checkpoint file on successful completion
     if std::path::Path::new(checkpoint_file).exists() {
-        if let Err(e) = std::fs::remove_file(checkpoint_file) {
-            eprintln!("Warning: Could not delete checkpoint file: {}", e);
+        delete_search_checkpoint(checkpoint_file);
+    }
+}
+
+/// One progress line of a search, without a percentage when the total is unknown
+fn print_search_progress(
+    tested: u64,
+    total: Option<&BigUint>,
+    current: &BigUint,
+    is_checkpoint: bool,
+) {
+    let tested_part = match total {
+        Some(total) => {
+            let total_f64 = total.to_f64().unwrap_or(f64::MAX);
+            let progress = (tested as f64 / total_f64) * 100.0;
+            format!("{}/{} ({:.1}%)", tested, total, progress)
         }
+        None => tested.to_string(),
+    };
+    if is_checkpoint {
+        println!(
+            "[Search] Tested: {} | Current: {} | ✓ Checkpoint saved",
+            tested_part, current
+        );
+    } else {
+        println!("[Search] Tested: {} | Current: {}", tested_part, current);
     }
 }
 
-fn print_search_results(results: &SearchResults, elapsed: std::time::Duration) {
+/// Checkpoint file name part describing a number source
+fn file_name_part(source: &NumberSource) -> String {
+    source
+        .to_string()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn print_search_results(results: &SearchResults, elapsed: std::time::Duration, radix: u32) {
     println!("Search complete!");
     println!("  Total tested: {}", results.total_tested);
     println!(
@@ -924,18 +1845,92 @@ fn print_search_results(results: &SearchResults, elapsed: std::time::Duration) {
         "  Numbers reaching palindromes: {}",
         results.palindromes_found.len()
     );
+    println!(
+        "  Numbers already palindromes: {}",
+        results.palindrome_starts
+    );
+    if let Some(rate) = results.cache_hit_rate() {
+        println!("  Cache hit rate: {:.1}%", rate * 100.0);
+    }
     println!("  Time elapsed: {:.3}s", elapsed.as_secs_f64());
 
+    for line in statistics_lines(&results.statistics, radix) {
+        println!("{}", line);
+    }
+
     if !results.potential_lychrel.is_empty() {
         println!("\nPotential Lychrel numbers:");
         for result in &results.potential_lychrel {
-            println!("  - {}", result.start_number);
+            let number = result.start_number.to_str_radix(radix);
+            match &result.classification {
+                Some(Classification::Seed) => println!("  - {} (seed)", number),
+                Some(Classification::Kin { seed, joined_at }) => println!(
+                    "  - {} (kin of {}, joins at iteration {})",
+                    number,
+                    seed.to_str_radix(radix),
+                    joined_at
+                ),
+                _ => println!("  - {}", number),
+            }
+        }
+    }
+}
+
+/// Iteration table (count and most-delayed number per iteration count) and
+/// potential Lychrel counts per digit length, ready to print
+fn statistics_lines(stats: &SearchStatistics, radix: u32) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    if !stats.iteration_histogram.is_empty() {
+        lines.push(String::new());
+        lines.push("Iterations to palindrome:".to_string());
+        lines.push(format!(
+            "  {:>10}  {:>12}  {}",
+            "iterations", "numbers", "most delayed"
+        ));
+        for (iterations, count) in &stats.iteration_histogram {
+            let most_delayed = stats
+                .most_delayed
+                .get(iterations)
+                .map(|n| n.to_str_radix(radix))
+                .unwrap_or_default();
+            lines.push(format!(
+                "  {:>10}  {:>12}  {}",
+                iterations, count, most_delayed
+            ));
         }
     }
+
+    if !stats.lychrel_by_digits.is_empty() {
+        lines.push(String::new());
+        lines.push("Potential Lychrel numbers by digit length:".to_string());
+        for (digits, count) in &stats.lychrel_by_digits {
+            lines.push(format!("  {:>3} digits: {}", digits, count));
+        }
+    }
+
+    lines
+}
+
+/// JSON layout of saved search results
+#[derive(serde::Serialize)]
+struct SearchReport<'a> {
+    total_tested: u64,
+    palindromes_found: usize,
+    palindrome_starts: u64,
+    potential_lychrel: &'a [IterationResult],
+    statistics: &'a SearchStatistics,
 }
 
 fn save_results_to_file(results: &SearchResults, filename: &str) {
-    match serde_json::to_string_pretty(&results.potential_lychrel) {
+    let report = SearchReport {
+        total_tested: results.total_tested,
+        palindromes_found: results.palindromes_found.len(),
+        palindrome_starts: results.palindrome_starts,
+        potential_lychrel: &results.potential_lychrel,
+        statistics: &results.statistics,
+    };
+    match serde_json::to_string_pretty(&report) {
         Ok(json) => match File::create(filename) {
             Ok(mut file) => {
                 if let Err(e) = file.write_all(json.as_bytes()) {
@@ -976,9 +1971,12 @@ fn run_benchmark() {
         start: BigUint::from(1u64),
         end: BigUint::from(10000u64),
         max_iterations: 1000,
+        radix: 10,
+        classify: false,
         parallel: true,
         checkpoint_interval: None,
         checkpoint_file: None,
+        cache_size: None,
     };
     let start_time = Instant::now();
     let results = search_range(config);
@@ -995,9 +1993,12 @@ fn run_benchmark() {
         start: BigUint::from(1u64),
         end: BigUint::from(100000u64),
         max_iterations: 1000,
+        radix: 10,
+        classify: false,
         parallel: true,
         checkpoint_interval: None,
         checkpoint_file: None,
+        cache_size: None,
     };
     let start_time = Instant::now();
     let results = search_range(config_intensive);
@@ -1042,9 +2043,22 @@ fn init_config_file(output: &str) {
             println!("  Target final digits: {}", config.target_final_digits);
             println!("  Cache size:          {}", config.cache_size);
             println!("  Generator mode:      {:?}", config.generator_mode);
-            println!("  Checkpoint interval: {}", config.checkpoint_interval);
+            println!(
+                "  Checkpoint interval: {}",
+                interval_description(config.checkpoint_interval, config.checkpoint_interval_secs)
+            );
             println!("  Checkpoint file:     {}", config.checkpoint_file);
+            println!(
+                "  Stats interval:      {}",
+                interval_description(config.stats_interval, config.stats_interval_secs)
+            );
+            println!("  Stop when:           {}", stop_description(&config.stop));
+            println!(
+                "  Filters:             {}",
+                filter_description(&config.filters)
+            );
             println!("  Warmup:              {}", config.warmup);
+            println!("  Base:                {}", config.radix);
             println!("\nYou can now edit this file and use:");
             println!("  cargo run --release -- hunt-record --config {}", output);
         }
@@ -1101,14 +2115,75 @@ fn hunt_records_from_config(overrides: HuntOverrides) {
     if let Some(v) = overrides.checkpoint_interval {
         config.checkpoint_interval = v;
     }
+    if let Some(v) = overrides.checkpoint_interval_secs {
+        config.checkpoint_interval_secs = (v > 0).then_some(v);
+    }
+    if let Some(v) = overrides.stats_interval {
+        config.stats_interval = v;
+    }
+    if let Some(v) = overrides.stats_interval_secs {
+        config.stats_interval_secs = (v > 0).then_some(v);
+    }
     if let Some(v) = overrides.checkpoint_file {
         config.checkpoint_file = v;
     }
+    if let Some(v) = overrides.radix {
+        config.radix = v;
+    }
+    if let Some(v) = overrides.max_duration {
+        config.stop.max_duration_secs = (v > 0).then_some(v);
+    }
+    if let Some(v) = overrides.max_numbers {
+        config.stop.max_numbers = (v > 0).then_some(v);
+    }
+    if let Some(v) = overrides.max_records {
+        config.stop.max_records = (v > 0).then_some(v);
+    }
+    if let Some(v) = overrides.stop_at_iterations {
+        config.stop.iterations = (v > 0).then_some(v);
+    }
+    if let Some(v) = overrides.stop_at {
+        config.stop.position = Some(v.to_string());
+    }
 
-    hunt_records_with_config(config);
+    hunt_records_with_config(config, overrides.force_restart);
 }
 
-fn hunt_records_with_config(config: HuntConfig) {
+fn hunt_records_with_config(mut config: HuntConfig, force_restart: bool) {
+    let checkpoint_path = std::path::Path::new(&config.checkpoint_file).to_path_buf();
+    let mut resume_from = None;
+
+    if force_restart {
+        if checkpoint_path.exists() {
+            println!("Deleting existing checkpoint (--force-restart)...\n");
+            remove_hunt_checkpoint(&checkpoint_path);
+        }
+    } else if let Ok(checkpoint) = RecordHuntCheckpoint::load(&checkpoint_path) {
+        println!("========================================");
+        println!("  HUNT CHECKPOINT FOUND!");
+        println!("========================================");
+        checkpoint.display_info();
+        println!("========================================");
+        println!("\nDo you want to resume from this checkpoint?");
+        println!("  [Y] Resume from checkpoint (default)");
+        println!("  [N] Start fresh (delete checkpoint)");
+        print!("\nYour choice (Y/n): ");
+        std::io::Write::flush(&mut std::io::stdout()).unwrap();
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).unwrap();
+        let input = input.trim().to_lowercase();
+
+        if input.is_empty() || input == "y" || input == "yes" {
+            println!("\nResuming hunt from checkpoint...\n");
+            config = checkpoint.hunt_config(config);
+            resume_from = Some(checkpoint);
+        } else {
+            println!("\nDeleting old checkpoint and starting fresh...\n");
+            remove_hunt_checkpoint(&checkpoint_path);
+        }
+    }
+
     println!("🔍 ═══════════════════════════════════════");
     println!("   LYCHREL RECORD HUNT");
     println!("═════════════════════════════════════════");
@@ -1133,17 +2208,37 @@ fn hunt_records_with_config(config: HuntConfig) {
     println!("  Cache size:          {}", config.cache_size);
     println!("  Generator mode:      {:?}", config.generator_mode);
     println!(
-        "  Checkpoint interval: {} numbers",
-        config.checkpoint_interval
+        "  Checkpoint interval: {}",
+        interval_description(config.checkpoint_interval, config.checkpoint_interval_secs)
     );
     println!("  Checkpoint file:     {}", config.checkpoint_file);
+    println!(
+        "  Stats interval:      {}",
+        interval_description(config.stats_interval, config.stats_interval_secs)
+    );
+    println!("  Stop when:           {}", stop_description(&config.stop));
+    println!(
+        "  Filters:             {}",
+        filter_description(&config.filters)
+    );
     println!("  Warmup:              {}", config.warmup);
+    println!("  Base:                {}", config.radix);
     println!("═════════════════════════════════════════\n");
 
     let warmup = config.warmup;
+    let radix = config.radix;
 
     // Create hunter
-    let mut hunter = RecordHunter::new(config);
+    let mut hunter = match resume_from {
+        Some(ref checkpoint) => match RecordHunter::from_checkpoint(config, checkpoint) {
+            Ok(hunter) => hunter,
+            Err(e) => {
+                eprintln!("Error: Failed to resume from checkpoint: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => RecordHunter::new(config),
+    };
 
     // Warmup if requested
     if warmup {
@@ -1151,6 +2246,7 @@ fn hunt_records_with_config(config: HuntConfig) {
     }
 
     // Start hunting
+    install_interrupt_handler();
     let results = hunter.hunt();
 
     // Summary
@@ -1168,6 +2264,7 @@ fn hunt_records_with_config(config: HuntConfig) {
         "Time elapsed:        {:.2}s",
         results.elapsed_time.as_secs_f64()
     );
+    println!("Ended by:            {}", results.stop_reason);
 
     if results.elapsed_time.as_secs() > 0 {
         let rate = results.numbers_tested as f64 / results.elapsed_time.as_secs() as f64;
@@ -1181,7 +2278,9 @@ fn hunt_records_with_config(config: HuntConfig) {
         for record in &results.records {
             println!(
                 "  - Number: {} ({} iterations to palindrome, {} final digits)",
-                record.number, record.iterations, record.final_digits
+                in_radix(&record.number, radix),
+                record.iterations,
+                record.final_digits
             );
         }
         println!();
@@ -1192,9 +2291,142 @@ fn hunt_records_with_config(config: HuntConfig) {
         for candidate in &results.candidates_above_200 {
             println!(
                 "  - {} ({} iter to palindrome, {} digits)",
-                candidate.number, candidate.iterations, candidate.final_digits
+                in_radix(&candidate.number, radix),
+                candidate.iterations,
+                candidate.final_digits
             );
         }
         println!();
     }
+    exit_if_interrupted(&checkpoint_path.to_string_lossy());
+
+    if results.stop_reason != StopReason::RangeExhausted {
+        println!(
+            "⏸  Hunt stopped ({}). Checkpoint saved to {}",
+            results.stop_reason,
+            checkpoint_path.display()
+        );
+        println!("   Run the same command again to continue it.");
+        return;
+    }
+
+    // Clean up checkpoint files on successful completion
+    if checkpoint_path.exists() {
+        remove_hunt_checkpoint(&checkpoint_path);
+    }
+}
+
+/// Describe the stop conditions of a hunt
+fn stop_description(stop: &StopConditions) -> String {
+    let mut parts = Vec::new();
+    if let Some(secs) = stop.max_duration_secs {
+        parts.push(format!("after {}s", secs));
+    }
+    if let Some(n) = stop.max_numbers {
+        parts.push(format!("after {} numbers", n));
+    }
+    if let Some(n) = stop.max_records {
+        parts.push(format!("after {} records", n));
+    }
+    if let Some(n) = stop.iterations {
+        parts.push(format!("at a {}-iteration palindrome", n));
+    }
+    if let Some(ref position) = stop.position {
+        parts.push(format!("at position {}", position));
+    }
+    if parts.is_empty() {
+        "digit range exhausted".to_string()
+    } else {
+        parts.join(", or ")
+    }
+}
+
+/// Describe a hunt's filter pipeline, stages in order
+fn filter_description(filters: &[FilterStage]) -> String {
+    if filters.is_empty() {
+        return "none".to_string();
+    }
+    filters
+        .iter()
+        .map(|stage| stage.to_string())
+        .collect::<Vec<_>>()
+        .join(", then ")
+}
+
+/// Parse a duration such as `3600`, `90m`, `8h` or `2d` into seconds
+fn parse_duration_secs(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let (number, unit) = match s.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&s[..i], c.to_ascii_lowercase()),
+        _ => (s, 's'),
+    };
+    let scale = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 3600,
+        'd' => 86_400,
+        _ => {
+            return Err(format!(
+                "unknown duration unit '{}' (use s, m, h or d)",
+                unit
+            ))
+        }
+    };
+    number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(scale))
+        .ok_or_else(|| format!("invalid duration '{}'", s))
+}
+
+/// Let Ctrl-C and SIGTERM stop the running command with a final checkpoint
+fn install_interrupt_handler() {
+    if let Err(e) = interrupt::install_handler() {
+        eprintln!("Warning: Could not install the interrupt handler: {}", e);
+    }
+}
+
+/// After a stop request, keep the checkpoint and exit with status 130
+fn exit_if_interrupted(checkpoint_file: &str) {
+    if interrupt::stop_requested() {
+        println!("\n⏸  Interrupted. Checkpoint saved to {}", checkpoint_file);
+        println!("   Run the same command again to resume from it.");
+        std::process::exit(130);
+    }
+}
+
+/// Describe a hunt interval given in numbers and in seconds
+fn interval_description(numbers: u64, secs: Option<u64>) -> String {
+    match (numbers, secs.filter(|&s| s > 0)) {
+        (0, None) => "disabled".to_string(),
+        (n, None) => format!("every {} numbers", n),
+        (0, Some(s)) => format!("every {}s", s),
+        (n, Some(s)) => format!("every {} numbers or {}s", n, s),
+    }
+}
+
+/// Delete a hunt checkpoint together with the thread cache file saved next to it
+fn remove_hunt_checkpoint(checkpoint_path: &std::path::Path) {
+    let cache_file = RecordHuntCheckpoint::load(checkpoint_path)
+        .map(|checkpoint| checkpoint.thread_cache_file)
+        .ok();
+    if let Err(e) = std::fs::remove_file(checkpoint_path) {
+        eprintln!("Warning: Could not delete checkpoint file: {}", e);
+    }
+    if let Some(cache_file) = cache_file {
+        if std::path::Path::new(&cache_file).exists() {
+            if let Err(e) = std::fs::remove_file(&cache_file) {
+                eprintln!("Warning: Could not delete cache file: {}", e);
+            }
+        }
+    }
+}
+
+/// Render a decimal number string (as stored in records) in the given radix
+fn in_radix(decimal: &str, radix: u32) -> String {
+    match decimal.parse::<BigUint>() {
+        Ok(n) => n.to_str_radix(radix),
+        Err(_) => decimal.to_string(),
+    }
 }
@@ -0,0 +1,785 @@
+use crate::classification::classify_candidates;
+use crate::search::{
+    parse_bound, results_from_checkpoint, run_chunks, SearchConfig, SearchResults,
+};
+use crate::search_checkpoint::{
+    records_file, RecordLog, SearchCheckpoint, SearchCheckpointBuilder, SourceProgress,
+};
+use crate::thread_cache::ThreadCache;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, BufRead};
+use std::time::Instant;
+
+/// Numbers taken from a source at a time in parallel mode
+const BATCH_SIZE: usize = 10_000;
+
+/// Numbers of a batch handed to one parallel task
+const TASK_SIZE: usize = 100;
+
+/// Numbers read from a source, in source order
+pub type Numbers = Box<dyn Iterator<Item = io::Result<BigUint>>>;
+
+/// A set of numbers to search other than a plain range
+///
+/// Files and stdin hold one decimal number per line (scientific notation
+/// such as `1e25` is accepted, blank lines and lines starting with `#` are
+/// skipped). Masks and digit lengths are written in the search radix.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NumberSource {
+    /// Numbers listed in a file
+    File { path: String },
+    /// Numbers read from standard input
+    Stdin,
+    /// `count` numbers `start`, `start + step`, `start + 2 * step`, ...
+    Progression {
+        start: BigUint,
+        step: BigUint,
+        count: u64,
+    },
+    /// Every number matching a digit pattern where `?` stands for any digit,
+    /// e.g. `1??9?0`. A leading `?` does not take the digit 0, so all
+    /// numbers have the pattern's length.
+    Mask { pattern: String },
+    /// Every number with exactly this many digits
+    Digits { length: usize },
+}
+
+impl NumberSource {
+    /// Parse a source given on the command line
+    ///
+    /// Accepted forms are `file:PATH`, `stdin` (or `-`),
+    /// `progression:START,STEP,COUNT`, `mask:PATTERN` and `digits:N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lychrel_finder::NumberSource;
+    ///
+    /// let source = NumberSource::parse("mask:1??9").unwrap();
+    /// assert_eq!(source.count(10).unwrap(), 100u32.into());
+    /// assert!(NumberSource::parse("digits:x").is_err());
+    /// ```
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        if spec == "stdin" || spec == "-" {
+            return Ok(NumberSource::Stdin);
+        }
+
+        let (kind, value) = spec.split_once(':').ok_or_else(|| {
+            format!(
+                "invalid source '{}' (expected file:, stdin, progression:, mask: or digits:)",
+                spec
+            )
+        })?;
+        match kind {
+            "file" if !value.is_empty() => Ok(NumberSource::File {
+                path: value.to_string(),
+            }),
+            "progression" => {
+                let parts: Vec<&str> = value.split(',').collect();
+                let [start, step, count] = parts[..] else {
+                    return Err("a progression is written START,STEP,COUNT".to_string());
+                };
+                let count = count
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid count '{}'", count.trim()))?;
+                Ok(NumberSource::Progression {
+                    start: parse_bound(start)?,
+                    step: parse_bound(step)?,
+                    count,
+                })
+            }
+            "mask" => {
+                // Digits are checked against the radix once it is known
+                if value.is_empty() || !value.chars().all(|c| c == '?' || c.is_ascii_alphanumeric())
+                {
+                    return Err(format!("invalid mask '{}'", value));
+                }
+                Ok(NumberSource::Mask {
+                    pattern: value.to_string(),
+                })
+            }
+            "digits" => match value.trim().parse() {
+                Ok(length) if length > 0 => Ok(NumberSource::Digits { length }),
+                _ => Err(format!("invalid digit length '{}'", value)),
+            },
+            _ => Err(format!("invalid source '{}'", spec)),
+        }
+    }
+
+    /// Whether the numbers come from outside, so a resumed search has to
+    /// check it reads the same ones again
+    fn hashes_input(&self) -> bool {
+        matches!(self, NumberSource::File { .. } | NumberSource::Stdin)
+    }
+
+    /// How many numbers the source holds, `None` for files and stdin
+    pub fn count(&self, radix: u32) -> Option<BigUint> {
+        match self {
+            NumberSource::File { .. } | NumberSource::Stdin => None,
+            NumberSource::Progression { count, .. } => Some(BigUint::from(*count)),
+            NumberSource::Mask { pattern } => {
+                let mut count = BigUint::from(1u32);
+                for (i, c) in pattern.chars().enumerate() {
+                    if c == '?' {
+                        count *= if i == 0 { radix - 1 } else { radix };
+                    }
+                }
+                Some(count)
+            }
+            NumberSource::Digits { length } => {
+                let (low, high) = digit_bounds(*length, radix);
+                Some(high - low + 1u32)
+            }
+        }
+    }
+
+    /// Open the source
+    ///
+    /// # Arguments
+    ///
+    /// * `radix` - Base masks and digit lengths are read in
+    ///
+    /// # Returns
+    ///
+    /// The numbers in source order, or an error if the file cannot be opened
+    /// or the mask has digits outside the radix. Lines that are not numbers
+    /// come out as `InvalidData` errors.
+    pub fn numbers(&self, radix: u32) -> io::Result<Numbers> {
+        match self {
+            NumberSource::File { path } => {
+                let reader = io::BufReader::new(std::fs::File::open(path)?);
+                Ok(Box::new(parse_lines(reader.lines())))
+            }
+            NumberSource::Stdin => Ok(Box::new(parse_lines(io::stdin().lines()))),
+            NumberSource::Progression { start, step, count } => {
+                let step = step.clone();
+                let mut next = start.clone();
+                Ok(Box::new((0..*count).map(move |_| {
+                    let value = next.clone();
+                    next += &step;
+                    Ok(value)
+                })))
+            }
+            NumberSource::Mask { pattern } => Ok(Box::new(MaskNumbers::new(pattern, radix)?)),
+            NumberSource::Digits { length } => {
+                let (low, high) = digit_bounds(*length, radix);
+                Ok(Box::new(
+                    std::iter::successors(Some(low), move |n| (*n < high).then(|| n + 1u32))
+                        .map(Ok),
+                ))
+            }
+        }
+    }
+}
+
+impl fmt::Display for NumberSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberSource::File { path } => write!(f, "numbers from {}", path),
+            NumberSource::Stdin => write!(f, "numbers from stdin"),
+            NumberSource::Progression { start, step, count } => {
+                write!(f, "{} numbers from {} in steps of {}", count, start, step)
+            }
+            NumberSource::Mask { pattern } => write!(f, "numbers matching {}", pattern),
+            NumberSource::Digits { length } => write!(f, "all {}-digit numbers", length),
+        }
+    }
+}
+
+/// Smallest and largest number with `length` digits
+fn digit_bounds(length: usize, radix: u32) -> (BigUint, BigUint) {
+    let length = length.max(1) as u32;
+    let radix = BigUint::from(radix);
+    let low = if length == 1 {
+        BigUint::from(1u32)
+    } else {
+        radix.pow(length - 1)
+    };
+    (low, radix.pow(length) - 1u32)
+}
+
+fn parse_lines(
+    lines: impl Iterator<Item = io::Result<String>>,
+) -> impl Iterator<Item = io::Result<BigUint>> {
+    lines.enumerate().filter_map(|(i, line)| {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => return Some(Err(e)),
+        };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        Some(parse_bound(line).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", i + 1, e))
+        }))
+    })
+}
+
+/// Numbers matching a digit mask, in ascending order
+struct MaskNumbers {
+    digits: Vec<u8>,
+    /// Positions of the `?` digits, most significant first
+    wildcards: Vec<usize>,
+    radix: u32,
+    done: bool,
+}
+
+impl MaskNumbers {
+    fn new(pattern: &str, radix: u32) -> io::Result<Self> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+        let mut digits = Vec::with_capacity(pattern.len());
+        let mut wildcards = Vec::new();
+
+        for (i, c) in pattern.chars().enumerate() {
+            if c == '?' {
+                wildcards.push(i);
+                digits.push(u8::from(i == 0));
+            } else {
+                let digit = c.to_digit(radix).ok_or_else(|| {
+                    invalid(format!(
+                        "'{}' is not a base {} digit in mask {}",
+                        c, radix, pattern
+                    ))
+                })?;
+                digits.push(digit as u8);
+            }
+        }
+        if digits.len() > 1 && digits[0] == 0 {
+            return Err(invalid(format!("mask {} starts with 0", pattern)));
+        }
+
+        Ok(MaskNumbers {
+            done: digits.is_empty(),
+            digits,
+            wildcards,
+            radix,
+        })
+    }
+}
+
+impl Iterator for MaskNumbers {
+    type Item = io::Result<BigUint>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let value = BigUint::from_radix_be(&self.digits, self.radix).unwrap_or_default();
+
+        // Odometer step over the wildcard digits, lowest first
+        self.done = true;
+        for &position in self.wildcards.iter().rev() {
+            if u32::from(self.digits[position]) + 1 < self.radix {
+                self.digits[position] += 1;
+                self.done = false;
+                break;
+            }
+            self.digits[position] = u8::from(position == 0);
+        }
+        Some(Ok(value))
+    }
+}
+
+/// Search every number of a source
+///
+/// Same as `search_source_resumable` without progress reports.
+pub fn search_source(source: &NumberSource, config: &SearchConfig) -> io::Result<SearchResults> {
+    search_source_resumable(source, config, |_, _, _| {})
+}
+
+/// Search every number of a source with resumable progress
+///
+/// Numbers are tested in source order; in parallel mode they are taken in
+/// batches that are spread over the rayon pool. Checkpoints record the
+/// source and how many of its numbers are done, so a resumed search skips
+/// that many numbers. A resumed file or stdin search therefore needs the
+/// same input again, byte for byte up to where it stopped; checkpoints keep
+/// a hash of the numbers read so far, and resuming on other input fails.
+///
+/// # Arguments
+///
+/// * `source` - The numbers to test
+/// * `config` - Iteration limit, radix, classification, parallelism, checkpoint and
+///   cache settings; its `start` and `end` are not used
+/// * `progress_callback` - Receives the count tested, the last number tested and
+///   whether a checkpoint was just saved
+///
+/// # Returns
+///
+/// The search results, or the first error met while reading the source
+///
+/// # Examples
+///
+/// ```
+/// use lychrel_finder::{search_source_resumable, NumberSource, SearchConfig};
+/// use num_bigint::BigUint;
+///
+/// let config = SearchConfig {
+///     start: BigUint::default(),
+///     end: BigUint::default(),
+///     max_iterations: 500,
+///     radix: 10,
+///     classify: false,
+///     parallel: false,
+///     checkpoint_interval: None,
+///     checkpoint_file: None,
+///     cache_size: None,
+/// };
+///
+/// // 196 is the only 1?6 number that looks like a Lychrel number
+/// let source = NumberSource::Mask { pattern: "1?6".to_string() };
+/// let results = search_source_resumable(&source, &config, |_, _, _| {}).unwrap();
+/// assert_eq!(results.total_tested, 10);
+/// assert_eq!(results.potential_lychrel[0].start_number, BigUint::from(196u32));
+/// ```
+pub fn search_source_resumable<F>(
+    source: &NumberSource,
+    config: &SearchConfig,
+    progress_callback: F,
+) -> io::Result<SearchResults>
+where
+    F: FnMut(u64, &BigUint, bool),
+{
+    let numbers = source.numbers(config.radix)?;
+    search_numbers(
+        source,
+        config,
+        numbers,
+        (SearchResults::new(), None),
+        Taken {
+            items: 0,
+            hash: source.hashes_input().then(InputHash::new),
+        },
+        0.0,
+        progress_callback,
+    )
+}
+
+/// Continue a source search from its checkpoint
+///
+/// Fails with `InvalidData` if the source now holds fewer numbers than the
+/// checkpoint had done, or other ones.
+pub(crate) fn resume_source_search<F>(
+    source: &NumberSource,
+    checkpoint: &SearchCheckpoint,
+    progress_callback: F,
+) -> io::Result<SearchResults>
+where
+    F: FnMut(u64, &BigUint, bool),
+{
+    // Checkpoints from before source progress counted in numbers_tested
+    let progress = checkpoint
+        .source_progress
+        .clone()
+        .unwrap_or_else(|| SourceProgress {
+            items_done: checkpoint.numbers_tested,
+            parallel: checkpoint.parallel.is_some(),
+            input_hash: None,
+        });
+    let config = SearchConfig {
+        start: checkpoint.start_range.clone(),
+        end: checkpoint.end_range.clone(),
+        max_iterations: checkpoint.max_iterations,
+        radix: checkpoint.radix,
+        classify: checkpoint.classify,
+        parallel: progress.parallel,
+        checkpoint_interval: checkpoint.checkpoint_interval,
+        checkpoint_file: checkpoint.checkpoint_file.clone(),
+        cache_size: checkpoint.cache_size,
+    };
+
+    let mut numbers = source.numbers(config.radix)?;
+    let mut hash = source.hashes_input().then(InputHash::new);
+    let mut skipped = 0;
+    for number in numbers.by_ref().take(progress.items_done as usize) {
+        let number = number?;
+        if let Some(ref mut hash) = hash {
+            hash.add(&number);
+        }
+        skipped += 1;
+    }
+    if skipped < progress.items_done {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "{} ended after {} numbers, the checkpoint had done {}",
+                source, skipped, progress.items_done
+            ),
+        ));
+    }
+    if let (Some(saved), Some(ref hash)) = (progress.input_hash, &hash) {
+        if saved != hash.0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "the first {} numbers of {} differ from the ones the checkpoint was made with",
+                    progress.items_done, source
+                ),
+            ));
+        }
+    }
+    search_numbers(
+        source,
+        &config,
+        numbers,
+        results_from_checkpoint(checkpoint),
+        Taken {
+            items: progress.items_done,
+            hash,
+        },
+        checkpoint.elapsed_secs,
+        progress_callback,
+    )
+}
+
+fn search_numbers<F>(
+    source: &NumberSource,
+    config: &SearchConfig,
+    mut numbers: Numbers,
+    (mut results, mut log): (SearchResults, Option<RecordLog>),
+    mut taken: Taken,
+    base_elapsed: f64,
+    mut progress_callback: F,
+) -> io::Result<SearchResults>
+where
+    F: FnMut(u64, &BigUint, bool),
+{
+    let start_time = Instant::now();
+    let (max_iterations, radix) = (config.max_iterations, config.radix);
+    let mut cache = config.cache_size.map(ThreadCache::new);
+    let mut last_checkpoint = results.total_tested;
+    let batch_size = if config.parallel { BATCH_SIZE } else { 1 };
+
+    loop {
+        let batch = numbers
+            .by_ref()
+            .take(batch_size)
+            .collect::<io::Result<Vec<BigUint>>>()?;
+        let Some(current) = batch.last().cloned() else {
+            break;
+        };
+        if let Some(ref mut hash) = taken.hash {
+            batch.iter().for_each(|n| hash.add(n));
+        }
+        taken.items += batch.len() as u64;
+
+        if !config.parallel {
+            for number in batch {
+                results.test(&number, max_iterations, radix, cache.as_mut());
+            }
+        } else {
+            // Parts of the batch run like range chunks, merged back in source order
+            let mut parts = Vec::new();
+            run_chunks(
+                batch.chunks(TASK_SIZE).enumerate(),
+                &mut cache,
+                |(_, numbers), mut worker| {
+                    let mut part = SearchResults::new();
+                    for number in numbers.iter() {
+                        part.test(number, max_iterations, radix, worker.as_deref_mut());
+                    }
+                    part
+                },
+                |(index, _), part| parts.push((index, part)),
+            );
+            parts.sort_by_key(|&(index, _)| index);
+            for (_, part) in parts {
+                results.merge(part);
+            }
+        }
+
+        let stop = config.checkpoint_file.is_some() && crate::interrupt::stop_requested();
+        let should_save_checkpoint = stop
+            || if let Some(interval) = config.checkpoint_interval {
+                results.total_tested - last_checkpoint >= interval
+            } else {
+                false
+            };
+
+        if should_save_checkpoint {
+            if let Some(ref file) = config.checkpoint_file {
+                let saved = log
+                    .get_or_insert_with(|| RecordLog::new(records_file(file)))
+                    .append(&results)
+                    .and_then(|records| {
+                        SearchCheckpointBuilder::new()
+                            .source(source.clone())
+                            .source_progress(SourceProgress {
+                                items_done: taken.items,
+                                parallel: config.parallel,
+                                input_hash: taken.hash.map(|h| h.0),
+                            })
+                            .current_number(current.clone())
+                            .max_iterations(config.max_iterations)
+                            .radix(config.radix)
+                            .classify(config.classify)
+                            .numbers_tested(results.total_tested)
+                            .summary(results.summary())
+                            .records(records)
+                            .checkpoint_interval(config.checkpoint_interval)
+                            .checkpoint_file(config.checkpoint_file.clone())
+                            .cache_size(config.cache_size)
+                            .elapsed_secs(base_elapsed + start_time.elapsed().as_secs_f64())
+                            .build()
+                            .save(file)
+                    });
+
+                if let Err(e) = saved {
+                    eprintln!("Warning: Failed to save checkpoint: {}", e);
+                } else {
+                    progress_callback(results.total_tested, &current, true);
+                    last_checkpoint = results.total_tested;
+                    if stop {
+                        break;
+                    }
+                    continue;
+                }
+            }
+        }
+        progress_callback(results.total_tested, &current, false);
+        if stop {
+            break;
+        }
+    }
+
+    if config.classify {
+        classify_candidates(
+            &mut results.potential_lychrel,
+            config.max_iterations,
+            config.radix,
+        );
+    }
+    Ok(results)
+}
+
+/// Numbers a source search has taken from its source so far
+struct Taken {
+    items: u64,
+    hash: Option<InputHash>,
+}
+
+/// FNV-1a hash of the numbers read from a file or stdin
+#[derive(Debug, Clone, Copy)]
+struct InputHash(u64);
+
+impl InputHash {
+    fn new() -> Self {
+        InputHash(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn add(&mut self, number: &BigUint) {
+        // The length keeps 1, 2 apart from 258
+        let bytes = number.to_bytes_le();
+        for &byte in (bytes.len() as u64).to_le_bytes().iter().chain(&bytes) {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::search_range;
+
+    fn config(parallel: bool) -> SearchConfig {
+        SearchConfig {
+            start: BigUint::default(),
+            end: BigUint::default(),
+            max_iterations: 300,
+            radix: 10,
+            classify: false,
+            parallel,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            cache_size: None,
+        }
+    }
+
+    fn collect(source: &NumberSource, radix: u32) -> Vec<u64> {
+        source
+            .numbers(radix)
+            .unwrap()
+            .map(|n| u64::try_from(n.unwrap()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_sources_enumerate() {
+        let mask = NumberSource::parse("mask:1?9?").unwrap();
+        let numbers = collect(&mask, 10);
+        assert_eq!(numbers.len(), 100);
+        assert_eq!(numbers[..3], [1090, 1091, 1092]);
+        assert_eq!(numbers[99], 1999);
+        assert!(numbers.windows(2).all(|w| w[0] < w[1]));
+
+        // A leading wildcard skips 0, base 2 masks use base 2 digits
+        assert_eq!(collect(&NumberSource::parse("mask:??").unwrap(), 10)[0], 10);
+        assert_eq!(
+            collect(&NumberSource::parse("mask:1?1").unwrap(), 2),
+            vec![5, 7]
+        );
+        assert_eq!(
+            NumberSource::parse("mask:?2").unwrap().count(10).unwrap(),
+            9u32.into()
+        );
+        assert!(NumberSource::parse("mask:1a").unwrap().numbers(10).is_err());
+
+        let digits = NumberSource::parse("digits:2").unwrap();
+        assert_eq!(collect(&digits, 10), (10..100).collect::<Vec<_>>());
+        assert_eq!(digits.count(2).unwrap(), 2u32.into());
+
+        let progression = NumberSource::parse("progression:5,7,4").unwrap();
+        assert_eq!(collect(&progression, 10), vec![5, 12, 19, 26]);
+
+        for bad in [
+            "",
+            "mask:",
+            "digits:0",
+            "progression:1,2",
+            "file:",
+            "range:1",
+        ] {
+            assert!(
+                NumberSource::parse(bad).is_err(),
+                "{} should be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_file_source_matches_range() {
+        let path = std::env::temp_dir().join("lychrel_number_source.txt");
+        let mut text = String::from("# numbers 1 to 2000\n\n");
+        for n in 1..=2000 {
+            text.push_str(&format!("{}\n", n));
+        }
+        std::fs::write(&path, text).unwrap();
+        let source = NumberSource::File {
+            path: path.to_str().unwrap().to_string(),
+        };
+
+        let range = search_range(SearchConfig {
+            start: BigUint::from(1u32),
+            end: BigUint::from(2000u32),
+            ..config(false)
+        });
+        let candidates = |results: &SearchResults| -> Vec<(BigUint, u64)> {
+            results
+                .potential_lychrel
+                .iter()
+                .map(|r| (r.start_number.clone(), r.iterations))
+                .collect()
+        };
+        for (parallel, cache_size) in [(false, None), (true, None), (true, Some(10_000))] {
+            let config = SearchConfig {
+                cache_size,
+                ..config(parallel)
+            };
+            let results = search_source(&source, &config).unwrap();
+            assert_eq!(results.total_tested, 2000);
+            assert_eq!(results.statistics, range.statistics);
+            assert_eq!(results.palindrome_starts, range.palindrome_starts);
+            assert_eq!(candidates(&results), candidates(&range));
+            assert_eq!(results.cache_hits > 0, cache_size.is_some());
+        }
+
+        std::fs::write(&path, "12\nabc\n").unwrap();
+        let result = search_source(&source, &config(false));
+        std::fs::remove_file(&path).ok();
+        assert!(matches!(result, Err(e) if e.kind() == io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn test_resume_source_checkpoint() {
+        let path = std::env::temp_dir().join("lychrel_source_checkpoint.json");
+        let path = path.to_str().unwrap().to_string();
+        let source = NumberSource::Digits { length: 3 };
+        let full = search_source(&source, &config(false)).unwrap();
+
+        // Checkpoint as it would be written after the first 300 numbers
+        let first: Vec<_> = source.numbers(10).unwrap().take(300).collect();
+        let mut partial = SearchResults::new();
+        for n in first {
+            partial.test(&n.unwrap(), 300, 10, None);
+        }
+        SearchCheckpointBuilder::new()
+            .source(source.clone())
+            .current_number(BigUint::from(399u32))
+            .max_iterations(300)
+            .radix(10)
+            .numbers_tested(partial.total_tested)
+            .potential_lychrel(partial.potential_lychrel)
+            .checkpoint_interval(Some(300))
+            .checkpoint_file(Some(path.clone()))
+            .build()
+            .save(&path)
+            .unwrap();
+
+        let checkpoint = SearchCheckpoint::load(&path).unwrap();
+        assert_eq!(checkpoint.source, Some(source.clone()));
+        let resumed = resume_source_search(&source, &checkpoint, |_, _, _| {}).unwrap();
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(records_file(&path)).ok();
+
+        assert_eq!(resumed.total_tested, 900);
+        let starts = |r: &SearchResults| {
+            let mut starts: Vec<_> = r
+                .potential_lychrel
+                .iter()
+                .map(|r| r.start_number.clone())
+                .collect();
+            starts.sort();
+            starts
+        };
+        assert_eq!(starts(&resumed), starts(&full));
+    }
+
+    #[test]
+    fn test_resume_file_source_checks_input() {
+        let path = std::env::temp_dir().join("lychrel_source_input.txt");
+        let file = std::env::temp_dir().join("lychrel_source_input_checkpoint.json");
+        let file = file.to_str().unwrap().to_string();
+        let text: String = (100..1000).map(|n| format!("{}\n", n)).collect();
+        std::fs::write(&path, &text).unwrap();
+        let source = NumberSource::File {
+            path: path.to_str().unwrap().to_string(),
+        };
+
+        // The last checkpoint is saved after 800 numbers
+        let full = search_source_resumable(
+            &source,
+            &SearchConfig {
+                checkpoint_interval: Some(400),
+                checkpoint_file: Some(file.clone()),
+                ..config(false)
+            },
+            |_, _, _| {},
+        )
+        .unwrap();
+        let checkpoint = SearchCheckpoint::load(&file).unwrap();
+        let progress = checkpoint.source_progress.clone().unwrap();
+        assert_eq!(progress.items_done, 800);
+        assert!(progress.input_hash.is_some());
+
+        let resumed = resume_source_search(&source, &checkpoint, |_, _, _| {}).unwrap();
+        assert_eq!(resumed, full);
+
+        // Other numbers before the checkpoint, or too few of them, are refused
+        std::fs::write(&path, text.replacen("150\n", "151\n", 1)).unwrap();
+        let changed = resume_source_search(&source, &checkpoint, |_, _, _| {});
+        std::fs::write(&path, &text[..text.len() / 2]).unwrap();
+        let truncated = resume_source_search(&source, &checkpoint, |_, _, _| {});
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(&file).ok();
+        std::fs::remove_file(records_file(&file)).ok();
+        assert!(matches!(changed, Err(e) if e.kind() == io::ErrorKind::InvalidData));
+        assert!(matches!(truncated, Err(e) if e.kind() == io::ErrorKind::InvalidData));
+    }
+}
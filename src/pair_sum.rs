@@ -0,0 +1,340 @@
+use crate::digit_number::DigitNumber;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+/// Flag set on a pair sum while it holds the carry into its low position
+const CARRY_IN: u8 = 0x20;
+
+/// Bits holding the pair sum itself (0..=18)
+const SUM_MASK: u8 = 0x1f;
+
+/// Decimal digit of a low position, from a pair sum and its carry flag
+fn low_digit(entry: u8) -> u8 {
+    ((entry & SUM_MASK) + (entry & CARRY_IN != 0) as u8) % 10
+}
+
+/// A decimal number `P + reverse(P)` stored as the pair sums of `P`
+///
+/// Entry `i` holds `p_i + p_(len-1-i)` for the lower half of `P`, and an odd
+/// length adds the doubled middle digit. That is one byte per two digits,
+/// half the memory of a `DigitNumber`, and a reverse-add step turns these
+/// sums directly into the pair sums of the next value without ever storing
+/// its digits.
+#[derive(Debug, Clone)]
+pub struct PairSumNumber {
+    sums: Vec<u8>,
+    /// Digit count of `P`; the represented value has this many digits or one more
+    source_len: usize,
+    /// Carry into the upper half while `sums` holds carry flags
+    center_carry: Option<u8>,
+}
+
+/// Serializable pair-sum form, used by verification checkpoints
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PairSumState {
+    pub source_len: usize,
+    /// Pair sums written as base-19 digits (`0`-`9` then `a`-`i`)
+    pub sums: String,
+}
+
+impl PairSumNumber {
+    /// Represent `n + reverse(n)`, the value after the first reverse-add step
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lychrel_finder::pair_sum::PairSumNumber;
+    /// use num_bigint::BigUint;
+    ///
+    /// let n = PairSumNumber::after_first_step(&BigUint::from(196u32));
+    /// assert_eq!(n.to_biguint(), BigUint::from(887u32));
+    /// ```
+    pub fn after_first_step(n: &BigUint) -> Self {
+        Self::from_digit_number(&DigitNumber::from_biguint(n))
+    }
+
+    /// Represent `n + reverse(n)` for a decimal digit vector
+    ///
+    /// # Panics
+    ///
+    /// Panics if the digit vector is not in radix 10
+    pub fn from_digit_number(n: &DigitNumber) -> Self {
+        assert_eq!(n.radix(), 10, "pair sums are decimal");
+        let digits = n.digits();
+        let len = digits.len();
+        let sums = (0..len.div_ceil(2))
+            .map(|i| digits[i] + digits[len - 1 - i])
+            .collect();
+
+        PairSumNumber {
+            sums,
+            source_len: len,
+            center_carry: None,
+        }
+    }
+
+    /// Rebuild from a checkpointed state
+    ///
+    /// # Returns
+    ///
+    /// `None` if the state is inconsistent
+    pub fn from_state(state: &PairSumState) -> Option<Self> {
+        let sums = state
+            .sums
+            .chars()
+            .map(|c| c.to_digit(19).map(|d| d as u8))
+            .collect::<Option<Vec<u8>>>()?;
+
+        if state.source_len == 0 || sums.len() != state.source_len.div_ceil(2) {
+            return None;
+        }
+        // A doubled middle digit is always even
+        if state.source_len % 2 == 1 && sums[sums.len() - 1] % 2 == 1 {
+            return None;
+        }
+
+        Some(PairSumNumber {
+            sums,
+            source_len: state.source_len,
+            center_carry: None,
+        })
+    }
+
+    /// Serializable copy of the pair sums
+    pub fn to_state(&self) -> PairSumState {
+        PairSumState {
+            source_len: self.source_len,
+            sums: self
+                .sums
+                .iter()
+                .map(|&s| std::char::from_digit((s & SUM_MASK) as u32, 19).unwrap())
+                .collect(),
+        }
+    }
+
+    /// Resolve the carries and return the digits of the represented value
+    pub fn to_digit_number(&self) -> DigitNumber {
+        let len = self.source_len;
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+
+        // Position p and its mirror share the pair sum at the lower index
+        for position in 0..len {
+            let v = (self.sums[position.min(len - 1 - position)] & SUM_MASK) + carry;
+            digits.push(v % 10);
+            carry = (v >= 10) as u8;
+        }
+        if carry > 0 {
+            digits.push(1);
+        }
+
+        DigitNumber::from_digits_le(digits, 10)
+    }
+
+    /// Convert the represented value into a BigUint
+    pub fn to_biguint(&self) -> BigUint {
+        self.to_digit_number().to_biguint()
+    }
+
+    /// Number of digits of the represented value
+    pub fn len(&self) -> usize {
+        let center = self.center_carry.unwrap_or_else(|| self.center_carry_of());
+        self.source_len + self.top_carry(center) as usize
+    }
+
+    /// Check if the represented value has no digits (never true for a valid number)
+    pub fn is_empty(&self) -> bool {
+        self.source_len == 0
+    }
+
+    /// Carry into the upper half, without touching the flags
+    fn center_carry_of(&self) -> u8 {
+        self.sums
+            .iter()
+            .fold(0, |carry, &entry| ((entry & SUM_MASK) + carry >= 10) as u8)
+    }
+
+    /// Flag each lower entry (and the middle one) with the carry it receives
+    ///
+    /// # Returns
+    ///
+    /// The carry into the upper half
+    fn mark_carries(&mut self) -> u8 {
+        let mut carry = 0;
+        for entry in self.sums.iter_mut() {
+            let sum = *entry & SUM_MASK;
+            *entry = sum | (carry * CARRY_IN);
+            carry = (sum + carry >= 10) as u8;
+        }
+        carry
+    }
+
+    /// Whether resolving the value carries out of its top digit
+    ///
+    /// Scans from the top: a pair sum of 10 or more always carries out, 8 or
+    /// less never does, and only a run of 9s defers to the carry below it.
+    fn top_carry(&self, center_carry: u8) -> bool {
+        for &entry in &self.sums[..self.source_len / 2] {
+            match entry & SUM_MASK {
+                s if s >= 10 => return true,
+                s if s <= 8 => return false,
+                _ => {}
+            }
+        }
+        center_carry == 1
+    }
+
+    /// Check if the represented value reads the same forwards and backwards
+    fn check_palindrome(&mut self) -> bool {
+        let len = self.source_len;
+        let half = len / 2;
+        let odd = len % 2 == 1;
+        let center = self.mark_carries();
+        self.center_carry = Some(center);
+        let mid = if odd { low_digit(self.sums[half]) } else { 0 };
+
+        let top = self.top_carry(center);
+        if top {
+            // One extra leading 1, so position x mirrors position len - x
+            let lowest = if half == 0 {
+                mid
+            } else {
+                low_digit(self.sums[0])
+            };
+            if lowest != 1 {
+                return false;
+            }
+        }
+
+        let mut carry = center;
+        for i in (0..half).rev() {
+            let v = (self.sums[i] & SUM_MASK) + carry;
+            let high = v % 10;
+            carry = (v >= 10) as u8;
+
+            let mirror = if !top {
+                low_digit(self.sums[i])
+            } else if i + 1 < half {
+                low_digit(self.sums[i + 1])
+            } else if odd {
+                mid
+            } else {
+                // New middle digit of an odd-length value
+                continue;
+            };
+            if mirror != high {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Replace the pair sums of `P` by those of the value they represent
+    fn advance(&mut self) {
+        let center = match self.center_carry.take() {
+            Some(center) => center,
+            None => self.mark_carries(),
+        };
+        let len = self.source_len;
+        let half = len / 2;
+        let odd = len % 2 == 1;
+        let top = self.top_carry(center);
+        let mid = if odd { low_digit(self.sums[half]) } else { 0 };
+
+        let mut carry = center;
+        for i in (0..half).rev() {
+            let v = (self.sums[i] & SUM_MASK) + carry;
+            let high = v % 10;
+            carry = (v >= 10) as u8;
+
+            if !top {
+                self.sums[i] = low_digit(self.sums[i]) + high;
+            } else if i + 1 < half {
+                // The leading 1 shifts every mirror position up by one
+                self.sums[i + 1] = low_digit(self.sums[i + 1]) + high;
+            } else if odd {
+                self.sums[half] = mid + high;
+            } else {
+                self.sums.push(2 * high);
+            }
+        }
+
+        if top {
+            self.sums[0] = low_digit(self.sums[0]) + 1;
+            self.source_len = len + 1;
+        } else if odd {
+            self.sums[half] = 2 * mid;
+        }
+    }
+
+    /// Add the represented value to its reverse
+    ///
+    /// # Returns
+    ///
+    /// `true` if the new value is a palindrome
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lychrel_finder::pair_sum::PairSumNumber;
+    /// use num_bigint::BigUint;
+    ///
+    /// // 89 -> 187 -> 968
+    /// let mut n = PairSumNumber::after_first_step(&BigUint::from(89u32));
+    /// assert!(!n.reverse_add());
+    /// assert_eq!(n.to_biguint(), BigUint::from(968u32));
+    /// ```
+    pub fn reverse_add(&mut self) -> bool {
+        self.advance();
+        self.check_palindrome()
+    }
+
+    /// Check if the represented value is a palindrome
+    pub fn is_palindrome(&self) -> bool {
+        self.to_digit_number().is_palindrome()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_digit_engine() {
+        for start in 1u32..2000 {
+            let n = BigUint::from(start);
+            let mut digits = DigitNumber::from_biguint(&n);
+            let first_palindrome = digits.reverse_add();
+            let mut pairs = PairSumNumber::after_first_step(&n);
+            assert_eq!(pairs.to_digit_number(), digits, "start {}", start);
+            assert_eq!(pairs.is_palindrome(), first_palindrome);
+
+            for _ in 0..60 {
+                let palindrome = digits.reverse_add();
+                assert_eq!(pairs.reverse_add(), palindrome, "start {}", start);
+                assert_eq!(pairs.to_digit_number(), digits, "start {}", start);
+                assert_eq!(pairs.len(), digits.len());
+                if palindrome {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_state_roundtrip() {
+        let mut n = PairSumNumber::after_first_step(&BigUint::from(196u32));
+        for _ in 0..100 {
+            n.reverse_add();
+        }
+        let state = n.to_state();
+        let restored = PairSumNumber::from_state(&state).unwrap();
+        assert_eq!(restored.to_biguint(), n.to_biguint());
+
+        let bad = PairSumState {
+            source_len: 3,
+            sums: "a3".to_string(),
+        };
+        assert!(PairSumNumber::from_state(&bad).is_none());
+    }
+}
@@ -2,7 +2,8 @@ use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-use crate::record_hunt::{HuntStatistics, RecordCandidate};
+use crate::hunt_filter::{default_filters, FilterStage, StageCounts};
+use crate::record_hunt::{HuntConfig, HuntStatistics, RecordCandidate};
 use crate::seed_generator::GeneratorMode;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -19,6 +20,9 @@ pub struct GeneratorState {
     pub current_value: String, // BigUint as String for serialization
     pub digits: usize,
     pub mode: GeneratorMode,
+    /// Numbers generated so far at the current digit length
+    #[serde(default)]
+    pub range_tested: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -27,9 +31,15 @@ pub struct CheckpointStatistics {
     pub seeds_tested: u64,
     pub cache_hits: u64,
     pub cache_misses: u64,
-    pub best_iterations_found: u32,
+    pub best_iterations_found: u64,
     pub best_digits_found: usize,
     pub candidates_above_200: Vec<RecordCandidate>,
+    /// Hunting time before the checkpoint, carried on by a resumed hunt
+    #[serde(default)]
+    pub elapsed_secs: f64,
+    /// Counters of each filter stage, in pipeline order
+    #[serde(default)]
+    pub filter_counts: Vec<StageCounts>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -37,17 +47,27 @@ pub struct CheckpointConfig {
     pub min_digits: usize,
     #[serde(default)]
     pub max_digits: Option<usize>,
-    pub target_iterations: u32,
-    pub max_iterations: u32,
+    pub target_iterations: u64,
+    pub max_iterations: u64,
     pub target_final_digits: usize,
     pub cache_size: usize,
     pub checkpoint_interval: u64,
+    #[serde(default = "default_radix")]
+    pub radix: u32,
+    /// Filter pipeline of the hunt; checkpoints without one used the default
+    #[serde(default = "default_filters")]
+    pub filters: Vec<FilterStage>,
+}
+
+fn default_radix() -> u32 {
+    10
 }
 
 impl RecordHuntCheckpoint {
     pub fn new(
         current_position: &BigUint,
         digits: usize,
+        range_tested: u64,
         mode: GeneratorMode,
         stats: &HuntStatistics,
         cache_file: &str,
@@ -58,6 +78,7 @@ impl RecordHuntCheckpoint {
                 current_value: current_position.to_string(),
                 digits,
                 mode,
+                range_tested,
             },
             statistics: CheckpointStatistics {
                 numbers_tested: stats.numbers_tested,
@@ -67,6 +88,8 @@ impl RecordHuntCheckpoint {
                 best_iterations_found: stats.best_iterations_found,
                 best_digits_found: stats.best_digits_found,
                 candidates_above_200: stats.candidates_above_200.clone(),
+                elapsed_secs: stats.start_time.elapsed().as_secs_f64(),
+                filter_counts: stats.filter_counts.clone(),
             },
             thread_cache_file: cache_file.to_string(),
             timestamp: chrono::Local::now().to_string(),
@@ -86,6 +109,27 @@ impl RecordHuntCheckpoint {
         self.generator_state.current_value.parse()
     }
 
+    /// The hunt this checkpoint belongs to, run with the cache size,
+    /// checkpoint settings, stop conditions and filter pipeline of `base`
+    ///
+    /// Digit range, targets, iteration limit, base and generator mode are
+    /// taken from the checkpoint, so a resumed hunt goes on with the same
+    /// search it stopped in. The cache is loaded from the checkpoint, so
+    /// there is no warmup.
+    pub fn hunt_config(&self, base: HuntConfig) -> HuntConfig {
+        HuntConfig {
+            min_digits: self.config.min_digits,
+            max_digits: self.config.max_digits,
+            target_iterations: self.config.target_iterations,
+            max_iterations: self.config.max_iterations,
+            target_final_digits: self.config.target_final_digits,
+            generator_mode: self.generator_state.mode.clone(),
+            radix: self.config.radix,
+            warmup: false,
+            ..base
+        }
+    }
+
     pub fn display_info(&self) {
         println!("📂 Checkpoint Information");
         println!("  Timestamp: {}", self.timestamp);
@@ -100,15 +144,28 @@ impl RecordHuntCheckpoint {
             "  Candidates (200+): {}",
             self.statistics.candidates_above_200.len()
         );
+        println!("  Elapsed time: {:.2}s", self.statistics.elapsed_secs);
+        println!("  Current digits: {}", self.generator_state.digits);
         println!("  Current position: {}", self.generator_state.current_value);
         println!("  Cache file: {}", self.thread_cache_file);
+        for (stage, counts) in self
+            .config
+            .filters
+            .iter()
+            .zip(&self.statistics.filter_counts)
+        {
+            println!(
+                "  Filter {}: {} passed, {} rejected",
+                stage, counts.passed, counts.rejected
+            );
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::record_hunt::HuntStatistics;
+    use crate::record_hunt::{HuntStatistics, RecordHunter};
     use std::time::Instant;
 
     #[test]
@@ -124,6 +181,7 @@ mod tests {
             best_digits_found: 75,
             start_time: Instant::now(),
             candidates_above_200: vec![],
+            filter_counts: vec![],
         };
 
         let config = CheckpointConfig {
@@ -134,11 +192,14 @@ mod tests {
             target_final_digits: 142,
             cache_size: 1000000,
             checkpoint_interval: 100000,
+            radix: 10,
+            filters: default_filters(),
         };
 
         let checkpoint = RecordHuntCheckpoint::new(
             &BigUint::from(123456789u64),
             23,
+            0,
             GeneratorMode::Sequential,
             &stats,
             "cache.json",
@@ -170,6 +231,7 @@ mod tests {
             best_digits_found: 0,
             start_time: Instant::now(),
             candidates_above_200: vec![],
+            filter_counts: vec![],
         };
 
         let config = CheckpointConfig {
@@ -180,12 +242,15 @@ mod tests {
             target_final_digits: 100,
             cache_size: 10000,
             checkpoint_interval: 10000,
+            radix: 10,
+            filters: default_filters(),
         };
 
         let position = BigUint::from(99999999999999999999u128);
         let checkpoint = RecordHuntCheckpoint::new(
             &position,
             20,
+            0,
             GeneratorMode::Sequential,
             &stats,
             "cache.json",
@@ -195,4 +260,42 @@ mod tests {
         let loaded_position = checkpoint.get_current_position().unwrap();
         assert_eq!(loaded_position, position);
     }
+
+    #[test]
+    fn test_resumed_hunt_matches_uninterrupted() {
+        let path = std::env::temp_dir().join("lychrel_hunt_resume_checkpoint.json");
+        let path = path.to_str().unwrap().to_string();
+        let config = |max_digits| HuntConfig {
+            min_digits: 3,
+            max_digits: Some(max_digits),
+            max_iterations: 100,
+            cache_size: 10_000,
+            checkpoint_interval: 1_000_000,
+            checkpoint_file: path.clone(),
+            ..HuntConfig::default()
+        };
+
+        let full = RecordHunter::new(config(4)).hunt();
+
+        // A 3-4 digit hunt stopped once its 3-digit range was done
+        let mut first = RecordHunter::new(config(3));
+        first.hunt();
+        first.max_digits = Some(4);
+        first.save_checkpoint();
+
+        let checkpoint = RecordHuntCheckpoint::load(Path::new(&path)).unwrap();
+        assert_eq!(checkpoint.generator_state.digits, 3);
+        assert_eq!(checkpoint.generator_state.range_tested, 900);
+        let mut resumed = RecordHunter::from_checkpoint(config(3), &checkpoint).unwrap();
+        assert_eq!(resumed.max_digits, Some(4));
+        assert!(!resumed.thread_cache.is_empty());
+        let results = resumed.hunt();
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(&checkpoint.thread_cache_file).ok();
+
+        assert_eq!(results.numbers_tested, full.numbers_tested);
+        assert_eq!(results.seeds_tested, full.seeds_tested);
+        assert_eq!(results.best_iterations_found, full.best_iterations_found);
+        assert_eq!(resumed.current_digits, 4);
+    }
 }
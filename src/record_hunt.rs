@@ -1,11 +1,14 @@
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 use std::time::{Duration, Instant};
 
-use crate::lychrel::{lychrel_iteration, lychrel_iteration_with_cache};
+use crate::hunt_filter::{apply_filters, default_filters, merge_counts, FilterStage, StageCounts};
+use crate::lychrel::lychrel_iteration_with_cache_radix;
+use crate::record_checkpoint::RecordHuntCheckpoint;
 use crate::seed_generator::{GeneratorMode, SeedGenerator};
 use crate::thread_cache::ThreadCache;
 
@@ -14,36 +17,172 @@ pub struct HuntConfig {
     pub min_digits: usize,
     #[serde(default)]
     pub max_digits: Option<usize>,
-    pub target_iterations: u32,
-    pub max_iterations: u32, // Max iterations before considering it a Lychrel
+    pub target_iterations: u64,
+    pub max_iterations: u64, // Max iterations before considering it a Lychrel
     pub target_final_digits: usize,
     pub cache_size: usize,
     #[serde(default = "default_generator_mode")]
     pub generator_mode: GeneratorMode,
+    /// Save a checkpoint once this many numbers were tested since the last one (0 to disable)
     pub checkpoint_interval: u64,
+    /// Also save a checkpoint once this many seconds passed since the last one
+    #[serde(default = "default_checkpoint_interval_secs")]
+    pub checkpoint_interval_secs: Option<u64>,
+    /// Print statistics once this many numbers were tested since they were last printed (0 to disable)
+    #[serde(default = "default_stats_interval")]
+    pub stats_interval: u64,
+    /// Also print statistics once this many seconds passed since they were last printed
+    #[serde(default)]
+    pub stats_interval_secs: Option<u64>,
     pub checkpoint_file: String,
     #[serde(default)]
     pub warmup: bool,
+    #[serde(default = "default_radix")]
+    pub radix: u32,
+    /// End the hunt early, for example after a fixed time window
+    #[serde(default)]
+    pub stop: StopConditions,
+    /// Quick checks a candidate must pass before its full test, in order
+    #[serde(default = "default_filters")]
+    pub filters: Vec<FilterStage>,
+}
+
+/// Conditions that end a hunt before its digit range is exhausted
+///
+/// They are checked between batches and count from the start of the
+/// current run, so a resumed hunt gets a fresh window. A hunt stopped by
+/// one of them saves a checkpoint to resume from.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StopConditions {
+    /// Stop after this many seconds
+    #[serde(default)]
+    pub max_duration_secs: Option<u64>,
+    /// Stop after this many numbers were tested
+    #[serde(default)]
+    pub max_numbers: Option<u64>,
+    /// Stop after this many records were found
+    #[serde(default)]
+    pub max_records: Option<usize>,
+    /// Stop once a number reaches a palindrome after at least this many iterations
+    #[serde(default)]
+    pub iterations: Option<u64>,
+    /// Stop when the generator reaches this number (decimal), before testing it
+    ///
+    /// Only the sequential and pattern modes move through positions.
+    #[serde(default)]
+    pub position: Option<String>,
+}
+
+/// Why a hunt ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StopReason {
+    /// Every number up to the maximum digit count was tested
+    RangeExhausted,
+    /// A stop was requested, usually by Ctrl-C
+    Interrupted,
+    MaxDuration,
+    MaxNumbers,
+    MaxRecords,
+    IterationsReached,
+    PositionReached,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StopReason::RangeExhausted => write!(f, "digit range exhausted"),
+            StopReason::Interrupted => write!(f, "interrupted"),
+            StopReason::MaxDuration => write!(f, "time limit reached"),
+            StopReason::MaxNumbers => write!(f, "number limit reached"),
+            StopReason::MaxRecords => write!(f, "record limit reached"),
+            StopReason::IterationsReached => write!(f, "iteration target reached"),
+            StopReason::PositionReached => write!(f, "stop position reached"),
+        }
+    }
+}
+
+/// What the current run of `hunt` has done, for the stop conditions
+struct RunProgress {
+    started: Instant,
+    numbers_at_start: u64,
+    records: usize,
+    best_palindrome: u64,
 }
 
 fn default_generator_mode() -> GeneratorMode {
     GeneratorMode::Sequential
 }
 
+fn default_radix() -> u32 {
+    10
+}
+
+fn default_checkpoint_interval_secs() -> Option<u64> {
+    Some(600)
+}
+
+fn default_stats_interval() -> u64 {
+    100_000
+}
+
+/// Fires once at least `every` numbers or `every_secs` seconds have passed
+/// since it last fired
+///
+/// The hunter checks it after every batch, so it works for any interval,
+/// not only for divisors of the batch totals.
+struct Interval {
+    every: u64,
+    every_secs: Option<u64>,
+    last_count: u64,
+    last_time: Instant,
+}
+
+impl Interval {
+    fn new(every: u64, every_secs: Option<u64>, count: u64) -> Self {
+        Interval {
+            every,
+            every_secs,
+            last_count: count,
+            last_time: Instant::now(),
+        }
+    }
+
+    fn is_due(&self, count: u64) -> bool {
+        (self.every > 0 && count - self.last_count >= self.every)
+            || self
+                .every_secs
+                .is_some_and(|secs| secs > 0 && self.last_time.elapsed().as_secs() >= secs)
+    }
+
+    fn reset(&mut self, count: u64) {
+        self.last_count = count;
+        self.last_time = Instant::now();
+    }
+}
+
 pub struct RecordHunter {
     pub min_digits: usize,
     pub max_digits: Option<usize>,
     pub current_digits: usize,
     pub current_range_tested: u64, // Count for current digit range only
-    pub target_iterations: u32,
-    pub max_iterations: u32,
+    pub target_iterations: u64,
+    pub max_iterations: u64,
     pub target_final_digits: usize,
     pub thread_cache: ThreadCache,
     pub seed_generator: SeedGenerator,
     pub generator_mode: GeneratorMode,
     pub stats: HuntStatistics,
     pub checkpoint_interval: u64,
+    pub checkpoint_interval_secs: Option<u64>,
+    pub stats_interval: u64,
+    pub stats_interval_secs: Option<u64>,
     pub checkpoint_file: String,
+    pub radix: u32,
+    pub stop: StopConditions,
+    pub filters: Vec<FilterStage>,
+    stop_position: Option<BigUint>,
+    checkpoint_due: Interval,
+    stats_due: Interval,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,17 +191,20 @@ pub struct HuntStatistics {
     pub seeds_tested: u64,
     pub cache_hits: u64,
     pub cache_misses: u64,
-    pub best_iterations_found: u32,
+    pub best_iterations_found: u64,
     pub best_digits_found: usize,
     #[serde(skip, default = "Instant::now")]
     pub start_time: Instant,
     pub candidates_above_200: Vec<RecordCandidate>,
+    /// Counters of each filter stage, in pipeline order
+    #[serde(default)]
+    pub filter_counts: Vec<StageCounts>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecordCandidate {
     pub number: String, // Store as String for serialization
-    pub iterations: u32,
+    pub iterations: u64,
     pub final_digits: usize,
     pub found_at: String,
 }
@@ -73,8 +215,9 @@ pub struct HuntResults {
     pub seeds_tested: u64,
     pub records: Vec<RecordCandidate>,
     pub candidates_above_200: Vec<RecordCandidate>,
-    pub best_iterations_found: u32,
+    pub best_iterations_found: u64,
     pub elapsed_time: Duration,
+    pub stop_reason: StopReason,
 }
 
 impl Default for HuntConfig {
@@ -88,8 +231,14 @@ impl Default for HuntConfig {
             cache_size: 1_000_000,
             generator_mode: GeneratorMode::Sequential,
             checkpoint_interval: 1_000_000,
+            checkpoint_interval_secs: default_checkpoint_interval_secs(),
+            stats_interval: default_stats_interval(),
+            stats_interval_secs: None,
             checkpoint_file: "hunt_checkpoint.json".to_string(),
             warmup: false,
+            radix: 10,
+            stop: StopConditions::default(),
+            filters: default_filters(),
         }
     }
 }
@@ -108,6 +257,14 @@ impl HuntConfig {
 
 impl RecordHunter {
     pub fn new(config: HuntConfig) -> Self {
+        let stop_position = config.stop.position.as_ref().and_then(|position| {
+            let parsed = position.parse().ok();
+            if parsed.is_none() {
+                eprintln!("Warning: Ignoring invalid stop position '{}'", position);
+            }
+            parsed
+        });
+
         RecordHunter {
             min_digits: config.min_digits,
             max_digits: config.max_digits,
@@ -117,7 +274,11 @@ impl RecordHunter {
             max_iterations: config.max_iterations,
             target_final_digits: config.target_final_digits,
             thread_cache: ThreadCache::new(config.cache_size),
-            seed_generator: SeedGenerator::new(config.min_digits, config.generator_mode.clone()),
+            seed_generator: SeedGenerator::with_radix(
+                config.min_digits,
+                config.generator_mode.clone(),
+                config.radix,
+            ),
             generator_mode: config.generator_mode,
             stats: HuntStatistics {
                 numbers_tested: 0,
@@ -128,9 +289,131 @@ impl RecordHunter {
                 best_digits_found: 0,
                 start_time: Instant::now(),
                 candidates_above_200: Vec::new(),
+                filter_counts: vec![StageCounts::default(); config.filters.len()],
             },
             checkpoint_interval: config.checkpoint_interval,
+            checkpoint_interval_secs: config.checkpoint_interval_secs,
+            stats_interval: config.stats_interval,
+            stats_interval_secs: config.stats_interval_secs,
             checkpoint_file: config.checkpoint_file,
+            radix: config.radix,
+            stop: config.stop,
+            filters: config.filters,
+            stop_position,
+            checkpoint_due: Interval::new(
+                config.checkpoint_interval,
+                config.checkpoint_interval_secs,
+                0,
+            ),
+            stats_due: Interval::new(config.stats_interval, config.stats_interval_secs, 0),
+        }
+    }
+
+    /// Rebuild a hunter from a checkpoint written by `save_checkpoint`
+    ///
+    /// The generator continues at the saved position and digit length, and
+    /// statistics, candidates, elapsed time and the thread cache are
+    /// restored. The hunt parameters come from the checkpoint (see
+    /// `RecordHuntCheckpoint::hunt_config`); `config` supplies the cache size
+    /// and checkpoint settings. If the cache file cannot be read, the hunt
+    /// goes on with an empty cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Configuration for the resumed run
+    /// * `checkpoint` - Checkpoint loaded from `config.checkpoint_file`
+    ///
+    /// # Returns
+    ///
+    /// The hunter, or an error if the saved generator position is invalid
+    pub fn from_checkpoint(
+        config: HuntConfig,
+        checkpoint: &RecordHuntCheckpoint,
+    ) -> std::io::Result<Self> {
+        let position = checkpoint.get_current_position().map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid generator position: {}", e),
+            )
+        })?;
+        let config = checkpoint.hunt_config(config);
+        let cache_size = config.cache_size;
+        let state = &checkpoint.generator_state;
+        let saved = &checkpoint.statistics;
+
+        let mut hunter = RecordHunter::new(config);
+        hunter.current_digits = state.digits;
+        hunter.current_range_tested = state.range_tested;
+        // A stop position the checkpoint already reached would end the
+        // resumed hunt before it tested anything
+        if hunter
+            .stop_position
+            .as_ref()
+            .is_some_and(|p| *p <= position)
+        {
+            println!(
+                "Note: Ignoring stop position {}, the checkpoint is already there",
+                position
+            );
+            hunter.stop.position = None;
+            hunter.stop_position = None;
+        }
+        let same_filters = hunter.filters == checkpoint.config.filters;
+        if !same_filters {
+            println!(
+                "Warning: The filter pipeline differs from the checkpoint's, using the configured one and resetting its counters"
+            );
+        }
+        hunter.seed_generator = SeedGenerator::from_checkpoint_with_radix(
+            state.digits,
+            state.mode.clone(),
+            position,
+            hunter.radix,
+        );
+        hunter.stats = HuntStatistics {
+            numbers_tested: saved.numbers_tested,
+            seeds_tested: saved.seeds_tested,
+            cache_hits: saved.cache_hits,
+            cache_misses: saved.cache_misses,
+            best_iterations_found: saved.best_iterations_found,
+            best_digits_found: saved.best_digits_found,
+            start_time: Instant::now()
+                .checked_sub(Duration::from_secs_f64(saved.elapsed_secs))
+                .unwrap_or_else(Instant::now),
+            candidates_above_200: saved.candidates_above_200.clone(),
+            // Counters of another pipeline, or of checkpoints from before
+            // the pipeline, say nothing about this one
+            filter_counts: if same_filters && saved.filter_counts.len() == hunter.filters.len() {
+                saved.filter_counts.clone()
+            } else {
+                vec![StageCounts::default(); hunter.filters.len()]
+            },
+        };
+        hunter.checkpoint_due.reset(saved.numbers_tested);
+        hunter.stats_due.reset(saved.numbers_tested);
+
+        match ThreadCache::load_from_file(Path::new(&checkpoint.thread_cache_file), cache_size) {
+            Ok(mut cache) => {
+                cache.restore_counts(saved.cache_hits, saved.cache_misses);
+                hunter.thread_cache = cache;
+            }
+            Err(e) => eprintln!(
+                "Warning: Could not load thread cache {}: {}",
+                checkpoint.thread_cache_file, e
+            ),
+        }
+        Ok(hunter)
+    }
+
+    /// Count of `d`-digit numbers tested in a digit range
+    /// This is an estimate since we filter seeds (approximately 50% of numbers)
+    fn numbers_in_digit_range(&self, d: usize) -> u64 {
+        let radix = self.radix as u64;
+        match (radix.checked_pow(d as u32), radix.checked_pow(d as u32 - 1)) {
+            // For smaller digits, calculate exactly
+            (Some(max_val), Some(min_val)) => (max_val - min_val) / 2,
+            // For larger digits, estimate (to avoid overflow)
+            _ => u64::MAX / 2,
         }
     }
 
@@ -141,18 +424,9 @@ impl RecordHunter {
         let mut total = 0u64;
 
         for d in self.min_digits..=max_d {
-            // For d digits: 10^d - 10^(d-1) numbers
+            // For d digits: radix^d - radix^(d-1) numbers
             // But we only test seeds (approx 50%), so divide by 2
-            let count_for_d = if d <= 18 {
-                // For smaller digits, calculate exactly
-                let max_val = 10u64.pow(d as u32);
-                let min_val = 10u64.pow((d - 1) as u32);
-                (max_val - min_val) / 2
-            } else {
-                // For larger digits, estimate (to avoid overflow)
-                u64::MAX / 2
-            };
-            total = total.saturating_add(count_for_d);
+            total = total.saturating_add(self.numbers_in_digit_range(d));
         }
 
         total
@@ -164,14 +438,7 @@ impl RecordHunter {
 
         // Add all numbers from completed digit ranges
         for d in self.min_digits..self.current_digits {
-            let count_for_d = if d <= 18 {
-                let max_val = 10u64.pow(d as u32);
-                let min_val = 10u64.pow((d - 1) as u32);
-                (max_val - min_val) / 2
-            } else {
-                u64::MAX / 2
-            };
-            processed = processed.saturating_add(count_for_d);
+            processed = processed.saturating_add(self.numbers_in_digit_range(d));
         }
 
         // Add numbers tested in current digit range
@@ -199,7 +466,13 @@ impl RecordHunter {
         let warmup_start = Instant::now();
 
         for n in 1u32..=1_000_000 {
-            lychrel_iteration_with_cache(BigUint::from(n), 1000, &mut self.thread_cache, None);
+            lychrel_iteration_with_cache_radix(
+                BigUint::from(n),
+                1000,
+                self.radix,
+                &mut self.thread_cache,
+                None,
+            );
 
             if n % 100_000 == 0 {
                 println!("  Warmup progress: {}/1,000,000", n);
@@ -225,6 +498,10 @@ impl RecordHunter {
     /// Lychrel numbers. It generates candidate numbers using the configured generator,
     /// tests them for Lychrel properties, and keeps track of statistics and records.
     /// The function also periodically saves checkpoints and displays progress.
+    /// It ends when the digit range is exhausted, when one of the stop
+    /// conditions is met, or on a stop request; the last two save a
+    /// checkpoint after the current batch. `HuntResults::stop_reason` tells
+    /// which one ended the run.
     ///
     /// # Returns
     ///
@@ -247,10 +524,24 @@ impl RecordHunter {
 
         println!("🎯 Starting record hunt (Parallel Mode)...\n");
 
+        let mut run = RunProgress {
+            started: Instant::now(),
+            numbers_at_start: self.stats.numbers_tested,
+            records: 0,
+            best_palindrome: 0,
+        };
+        let mut stop_reason = StopReason::RangeExhausted;
+
         loop {
+            if let Some(reason) = self.stop_reason(&run) {
+                self.save_checkpoint();
+                stop_reason = reason;
+                break;
+            }
+
             // 1. Parallel Generate a raw batch of candidates
             // Generate consecutive numbers efficiently
-            let batch_size = 500_000;
+            let batch_size = self.batch_size(&run, 500_000);
 
             // Advance the generator's state sequentially (very fast)
             let raw_batch = self.seed_generator.next_raw_batch(batch_size);
@@ -266,8 +557,11 @@ impl RecordHunter {
                             "\n📊 Moving to {}-digit numbers... (Overall progress: {:.2}%)\n",
                             self.current_digits, progress
                         );
-                        self.seed_generator =
-                            SeedGenerator::new(self.current_digits, self.generator_mode.clone());
+                        self.seed_generator = SeedGenerator::with_radix(
+                            self.current_digits,
+                            self.generator_mode.clone(),
+                            self.radix,
+                        );
                         continue;
                     }
                 }
@@ -287,63 +581,85 @@ impl RecordHunter {
                 cache_size: self.thread_cache.len(),
                 generator_mode: self.generator_mode.clone(),
                 checkpoint_interval: self.checkpoint_interval,
+                checkpoint_interval_secs: self.checkpoint_interval_secs,
+                stats_interval: self.stats_interval,
+                stats_interval_secs: self.stats_interval_secs,
                 checkpoint_file: self.checkpoint_file.clone(),
                 warmup: false,
+                radix: self.radix,
+                stop: self.stop.clone(),
+                filters: self.filters.clone(),
             };
 
             // 3. Process batch in parallel
-            let (results, merged_cache, seeds_tested, max_i, max_d) = raw_batch
-                .par_iter()
-                .fold(
-                    || {
-                        (
-                            Vec::new(),
-                            ThreadCache::new_worker(snapshot.clone(), worker_cache_size),
-                            0u64,
-                            0u32,
-                            0usize,
-                        )
-                    },
-                    |mut acc, candidate| {
-                        if !crate::seed_generator::is_potential_seed(candidate, Some(&p10_max)) {
-                            return acc;
-                        }
-
-                        acc.2 += 1;
-                        if let Some(r) = process_candidate(candidate, &mut acc.1, &config) {
-                            if r.iterations > acc.3 {
-                                acc.3 = r.iterations;
-                            }
-                            if r.final_digits > acc.4 {
-                                acc.4 = r.final_digits;
+            let (results, merged_cache, seeds_tested, max_i, max_d, max_p, filter_counts) =
+                raw_batch
+                    .par_iter()
+                    .fold(
+                        || {
+                            (
+                                Vec::new(),
+                                ThreadCache::new_worker(snapshot.clone(), worker_cache_size),
+                                0u64,
+                                0u64,
+                                0usize,
+                                0u64,
+                                vec![StageCounts::default(); config.filters.len()],
+                            )
+                        },
+                        |mut acc, candidate| {
+                            if !crate::seed_generator::is_potential_seed_radix(
+                                candidate,
+                                Some(&p10_max),
+                                config.radix,
+                            ) {
+                                return acc;
                             }
 
-                            if r.is_record || r.is_promising {
-                                acc.0.push(r);
+                            acc.2 += 1;
+                            if let Some(r) =
+                                process_candidate(candidate, &mut acc.1, &config, &mut acc.6)
+                            {
+                                if r.iterations > acc.3 {
+                                    acc.3 = r.iterations;
+                                }
+                                if r.final_digits > acc.4 {
+                                    acc.4 = r.final_digits;
+                                }
+                                if r.is_palindrome && r.iterations > acc.5 {
+                                    acc.5 = r.iterations;
+                                }
+
+                                if r.is_record || r.is_promising {
+                                    acc.0.push(r);
+                                }
                             }
-                        }
-                        acc
-                    },
-                )
-                .reduce(
-                    || {
-                        (
-                            Vec::new(),
-                            ThreadCache::new_empty(worker_cache_size),
-                            0u64,
-                            0u32,
-                            0usize,
-                        )
-                    },
-                    |mut a, b| {
-                        a.0.extend(b.0);
-                        a.1.merge(b.1);
-                        a.2 += b.2;
-                        a.3 = a.3.max(b.3);
-                        a.4 = a.4.max(b.4);
-                        a
-                    },
-                );
+                            acc
+                        },
+                    )
+                    .reduce(
+                        || {
+                            (
+                                Vec::new(),
+                                ThreadCache::new_empty(worker_cache_size),
+                                0u64,
+                                0u64,
+                                0usize,
+                                0u64,
+                                vec![StageCounts::default(); config.filters.len()],
+                            )
+                        },
+                        |mut a, b| {
+                            a.0.extend(b.0);
+                            a.1.merge(b.1);
+                            a.2 += b.2;
+                            a.3 = a.3.max(b.3);
+                            a.4 = a.4.max(b.4);
+                            a.5 = a.5.max(b.5);
+                            merge_counts(&mut a.6, &b.6);
+                            a
+                        },
+                    );
 
             // 4. Update state with results
             drop(snapshot); // CRITICAL: Release the Arc reference so merge is fast
@@ -361,9 +677,12 @@ impl RecordHunter {
             if max_d > self.stats.best_digits_found {
                 self.stats.best_digits_found = max_d;
             }
+            run.best_palindrome = run.best_palindrome.max(max_p);
+            merge_counts(&mut self.stats.filter_counts, &filter_counts);
 
             for res in results {
                 if res.is_record {
+                    run.records += 1;
                     self.handle_record_found(RecordCandidate {
                         number: res.number.clone(),
                         iterations: res.iterations,
@@ -388,19 +707,67 @@ impl RecordHunter {
             self.stats.cache_misses = cache_stats.misses;
 
             // 5. Periodic actions
-            if self
-                .stats
-                .numbers_tested
-                .is_multiple_of(self.checkpoint_interval)
-            {
+            let tested = self.stats.numbers_tested;
+            if self.checkpoint_due.is_due(tested) {
                 self.save_checkpoint();
+                self.checkpoint_due.reset(tested);
             }
-            if self.stats.numbers_tested.is_multiple_of(100_000) {
+            if self.stats_due.is_due(tested) {
                 self.print_stats();
+                self.stats_due.reset(tested);
             }
         }
 
-        self.finalize()
+        self.finalize(stop_reason)
+    }
+
+    /// The stop condition met by this run so far, if any
+    fn stop_reason(&self, run: &RunProgress) -> Option<StopReason> {
+        let stop = &self.stop;
+        let tested = self.stats.numbers_tested - run.numbers_at_start;
+        if crate::interrupt::stop_requested() {
+            Some(StopReason::Interrupted)
+        } else if stop
+            .max_duration_secs
+            .is_some_and(|secs| run.started.elapsed().as_secs() >= secs)
+        {
+            Some(StopReason::MaxDuration)
+        } else if stop.max_numbers.is_some_and(|max| tested >= max) {
+            Some(StopReason::MaxNumbers)
+        } else if stop.max_records.is_some_and(|max| run.records >= max) {
+            Some(StopReason::MaxRecords)
+        } else if stop
+            .iterations
+            .is_some_and(|iterations| run.best_palindrome >= iterations)
+        {
+            Some(StopReason::IterationsReached)
+        } else if self
+            .stop_position
+            .as_ref()
+            .is_some_and(|position| self.seed_generator.current_position() >= *position)
+        {
+            Some(StopReason::PositionReached)
+        } else {
+            None
+        }
+    }
+
+    /// Size of the next batch, cut short so the number limit and the stop
+    /// position are hit exactly
+    fn batch_size(&self, run: &RunProgress, size: usize) -> usize {
+        let mut size = size as u64;
+        if let Some(max) = self.stop.max_numbers {
+            let tested = self.stats.numbers_tested - run.numbers_at_start;
+            size = size.min(max.saturating_sub(tested));
+        }
+        if let Some(ref position) = self.stop_position {
+            let current = self.seed_generator.current_position();
+            if *position > current {
+                let remaining: BigUint = position - current;
+                size = size.min(u64::try_from(remaining).unwrap_or(u64::MAX));
+            }
+        }
+        size as usize
     }
 
     fn handle_record_found(&mut self, record: RecordCandidate) {
@@ -467,14 +834,37 @@ impl RecordHunter {
                 skip_rate * 100.0
             );
         }
+        if !self.filters.is_empty() {
+            println!("[Filters] {}", self.filter_summary());
+        }
+    }
+
+    /// Pass and reject counts of every filter stage on one line
+    fn filter_summary(&self) -> String {
+        self.filters
+            .iter()
+            .zip(&self.stats.filter_counts)
+            .map(|(stage, counts)| {
+                let mut part = format!(
+                    "{}: {} passed, {} rejected",
+                    stage, counts.passed, counts.rejected
+                );
+                if stage.dry_run {
+                    part += &format!(" ({} records)", counts.rejected_records);
+                }
+                part
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
     }
 
     pub fn save_checkpoint(&self) {
-        use crate::record_checkpoint::{CheckpointConfig, RecordHuntCheckpoint};
+        use crate::record_checkpoint::CheckpointConfig;
 
         let checkpoint = RecordHuntCheckpoint::new(
             &self.seed_generator.current_position(),
             self.current_digits,
+            self.current_range_tested,
             self.seed_generator.mode.clone(),
             &self.stats,
             &format!("{}_cache.json", self.checkpoint_file),
@@ -484,8 +874,10 @@ impl RecordHunter {
                 target_iterations: self.target_iterations,
                 max_iterations: self.max_iterations,
                 target_final_digits: self.target_final_digits,
-                cache_size: self.thread_cache.len(),
+                cache_size: self.thread_cache.max_size(),
                 checkpoint_interval: self.checkpoint_interval,
+                radix: self.radix,
+                filters: self.filters.clone(),
             },
         );
 
@@ -509,11 +901,15 @@ impl RecordHunter {
         }
     }
 
-    fn finalize(&self) -> HuntResults {
+    fn finalize(&self, stop_reason: StopReason) -> HuntResults {
         let elapsed = self.stats.start_time.elapsed();
 
         println!("\n🏁 ═══════════════════════════════════════════");
-        println!("   HUNT COMPLETE");
+        if stop_reason == StopReason::RangeExhausted {
+            println!("   HUNT COMPLETE");
+        } else {
+            println!("   HUNT STOPPED ({})", stop_reason);
+        }
         println!("═══════════════════════════════════════════");
         println!("Numbers tested:      {}", self.stats.numbers_tested);
         println!("Seeds tested:        {}", self.stats.seeds_tested);
@@ -524,6 +920,19 @@ impl RecordHunter {
             self.stats.candidates_above_200.len()
         );
         println!("Time elapsed:        {:.2}s", elapsed.as_secs_f64());
+        for (stage, counts) in self.filters.iter().zip(&self.stats.filter_counts) {
+            println!("Filter: {}", stage);
+            println!(
+                "  passed {}, rejected {}{}",
+                counts.passed,
+                counts.rejected,
+                if stage.dry_run {
+                    format!(", {} of them records", counts.rejected_records)
+                } else {
+                    String::new()
+                }
+            );
+        }
         println!("═══════════════════════════════════════════\n");
 
         // Find records (targets met) - all candidates are palindromes, not Lychrels
@@ -544,50 +953,48 @@ impl RecordHunter {
             candidates_above_200: self.stats.candidates_above_200.clone(),
             best_iterations_found: self.stats.best_iterations_found,
             elapsed_time: elapsed,
+            stop_reason,
         }
     }
 }
 
 struct ProcessResult {
     number: String,
-    iterations: u32,
+    iterations: u64,
     final_digits: usize,
+    is_palindrome: bool,
     is_record: bool,
     is_promising: bool,
 }
 
 /// Pure function to process a candidate
+///
+/// `filter_counts` holds this worker's counters of `config.filters`.
 fn process_candidate(
     candidate: &BigUint,
     cache: &mut ThreadCache,
     config: &HuntConfig,
+    filter_counts: &mut [StageCounts],
 ) -> Option<ProcessResult> {
-    // Phase 1: Quick filter (50 first iterations)
-    let quick_result = lychrel_iteration(candidate.clone(), 50);
+    // Phase 1: Quick filter pipeline
+    let dry_rejections = apply_filters(candidate, &config.filters, filter_counts, config.radix)?;
 
-    // Reject if growth too slow
-    let start_bits = candidate.bits();
-    let end_bits = quick_result
+    // Phase 2: Full test with cache
+    let result = lychrel_iteration_with_cache_radix(
+        candidate.clone(),
+        config.max_iterations,
+        config.radix,
+        cache,
+        None,
+    );
+
+    // Digit counts are measured in the hunt's radix
+    let final_digits = result
         .final_number
         .as_ref()
-        .map(|n| n.bits())
+        .map(|n| n.to_str_radix(config.radix).len())
         .unwrap_or(0);
 
-    // Growth threshold: 0.4 digits/iter => 20 digits in 50 iters
-    // 20 digits is approx 66 bits (20 / 0.301)
-    if (end_bits as i64 - start_bits as i64) < 66 {
-        return None; // Growth too slow
-    }
-
-    // Reject if palindrome found too quickly
-    if quick_result.is_palindrome {
-        return None;
-    }
-
-    // Phase 2: Full test with cache
-    let result =
-        lychrel_iteration_with_cache(candidate.clone(), config.max_iterations, cache, None);
-
     // Check for record or promising candidate
     // A record is a number that:
     // 1. REACHES a palindrome (not a Lychrel)
@@ -596,23 +1003,225 @@ fn process_candidate(
     let is_record = result.is_palindrome
         && result.iterations >= config.target_iterations
         && result.iterations <= config.max_iterations
-        && result
-            .final_number
-            .as_ref()
-            .map_or(0, |n| n.to_string().len())
-            >= config.target_final_digits;
+        && final_digits >= config.target_final_digits;
 
     let is_promising = result.is_palindrome && result.iterations >= 200;
 
+    if is_record {
+        for stage in dry_rejections {
+            filter_counts[stage].rejected_records += 1;
+        }
+    }
+
     Some(ProcessResult {
         number: candidate.to_string(),
         iterations: result.iterations,
-        final_digits: result
-            .final_number
-            .as_ref()
-            .map(|n| n.to_string().len())
-            .unwrap_or(0),
+        final_digits,
+        is_palindrome: result.is_palindrome,
         is_record,
         is_promising,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hunt_filter::FilterKind;
+
+    #[test]
+    fn test_interval_fires_on_count_or_time() {
+        let mut interval = Interval::new(300_000, None, 0);
+        assert!(!interval.is_due(200_000));
+        assert!(interval.is_due(500_000));
+        interval.reset(500_000);
+        assert!(!interval.is_due(700_000));
+        assert!(interval.is_due(1_000_000));
+
+        assert!(!Interval::new(0, None, 0).is_due(u64::MAX));
+        assert!(!Interval::new(0, Some(0), 0).is_due(1));
+
+        let mut timed = Interval::new(0, Some(1), 0);
+        assert!(!timed.is_due(1));
+        timed.last_time = Instant::now() - Duration::from_secs(2);
+        assert!(timed.is_due(1));
+    }
+
+    #[test]
+    fn test_checkpoint_interval_need_not_divide_batches() {
+        let path = std::env::temp_dir().join("lychrel_hunt_interval_checkpoint.json");
+        let path = path.to_str().unwrap().to_string();
+
+        // Batches of 900 and 9000 numbers, neither total a multiple of 1000
+        let mut hunter = RecordHunter::new(HuntConfig {
+            min_digits: 3,
+            max_digits: Some(4),
+            max_iterations: 100,
+            cache_size: 10_000,
+            checkpoint_interval: 1000,
+            checkpoint_interval_secs: None,
+            stats_interval: 0,
+            checkpoint_file: path.clone(),
+            ..HuntConfig::default()
+        });
+        hunter.hunt();
+
+        let checkpoint = RecordHuntCheckpoint::load(Path::new(&path)).unwrap();
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(&checkpoint.thread_cache_file).ok();
+        assert_eq!(checkpoint.statistics.numbers_tested, 9900);
+    }
+
+    #[test]
+    fn test_filter_counts_reach_stats_and_checkpoint() {
+        let path = std::env::temp_dir().join("lychrel_hunt_filter_checkpoint.json");
+        let path = path.to_str().unwrap().to_string();
+
+        let mut hunter = RecordHunter::new(HuntConfig {
+            checkpoint_interval: 1000,
+            ..stopping_config(&path, StopConditions::default())
+        });
+        hunter.hunt();
+        let counts = hunter.stats.filter_counts.clone();
+
+        // Each stage sees what the one before let through
+        assert_eq!(counts.len(), 2);
+        assert_eq!(
+            counts[0].passed + counts[0].rejected,
+            hunter.stats.seeds_tested
+        );
+        assert_eq!(counts[1].passed + counts[1].rejected, counts[0].passed);
+        assert!(counts[0].rejected > 0);
+
+        let checkpoint = RecordHuntCheckpoint::load(Path::new(&path)).unwrap();
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(&checkpoint.thread_cache_file).ok();
+        assert_eq!(checkpoint.statistics.filter_counts, counts);
+        assert_eq!(checkpoint.config.filters, default_filters());
+
+        let resumed = RecordHunter::from_checkpoint(HuntConfig::default(), &checkpoint).unwrap();
+        assert_eq!(resumed.stats.filter_counts, counts);
+
+        // A changed pipeline wins over the saved one, with fresh counters
+        let filters = vec![FilterStage::new(FilterKind::EarlyPalindrome {
+            iterations: 20,
+        })];
+        let resumed = RecordHunter::from_checkpoint(
+            HuntConfig {
+                filters: filters.clone(),
+                ..HuntConfig::default()
+            },
+            &checkpoint,
+        )
+        .unwrap();
+        assert_eq!(resumed.filters, filters);
+        assert_eq!(resumed.stats.filter_counts, vec![StageCounts::default()]);
+    }
+
+    fn stopping_config(path: &str, stop: StopConditions) -> HuntConfig {
+        HuntConfig {
+            min_digits: 3,
+            max_digits: Some(4),
+            max_iterations: 100,
+            cache_size: 10_000,
+            checkpoint_interval: 0,
+            checkpoint_interval_secs: None,
+            stats_interval: 0,
+            checkpoint_file: path.to_string(),
+            stop,
+            ..HuntConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_hunt_stops_on_conditions_and_resumes() {
+        let path = std::env::temp_dir().join("lychrel_hunt_stop_checkpoint.json");
+        let path = path.to_str().unwrap().to_string();
+
+        // The number limit cuts the batch short
+        let stop = StopConditions {
+            max_numbers: Some(5000),
+            ..StopConditions::default()
+        };
+        let results = RecordHunter::new(stopping_config(&path, stop)).hunt();
+        assert_eq!(results.stop_reason, StopReason::MaxNumbers);
+        assert_eq!(results.numbers_tested, 5000);
+
+        // A resumed run counts its limits afresh and finishes the range
+        let checkpoint = RecordHuntCheckpoint::load(Path::new(&path)).unwrap();
+        assert_eq!(checkpoint.statistics.numbers_tested, 5000);
+        let stop = StopConditions {
+            max_numbers: Some(5000),
+            ..StopConditions::default()
+        };
+        let results = RecordHunter::from_checkpoint(stopping_config(&path, stop), &checkpoint)
+            .unwrap()
+            .hunt();
+        assert_eq!(results.stop_reason, StopReason::RangeExhausted);
+        assert_eq!(results.numbers_tested, 9900);
+
+        // The stop position is left untested
+        let stop = StopConditions {
+            position: Some("500".to_string()),
+            ..StopConditions::default()
+        };
+        let results = RecordHunter::new(stopping_config(&path, stop)).hunt();
+        assert_eq!(results.stop_reason, StopReason::PositionReached);
+        assert_eq!(results.numbers_tested, 400);
+        let checkpoint = RecordHuntCheckpoint::load(Path::new(&path)).unwrap();
+        assert_eq!(checkpoint.generator_state.current_value, "500");
+
+        // Resumed with the same stop position, the hunt goes on past it
+        let stop = StopConditions {
+            position: Some("500".to_string()),
+            ..StopConditions::default()
+        };
+        let results = RecordHunter::from_checkpoint(stopping_config(&path, stop), &checkpoint)
+            .unwrap()
+            .hunt();
+        assert_eq!(results.stop_reason, StopReason::RangeExhausted);
+        assert_eq!(results.numbers_tested, 9900);
+
+        // 187 reaches a palindrome after 23 steps, in the first batch
+        let stop = StopConditions {
+            iterations: Some(20),
+            ..StopConditions::default()
+        };
+        let results = RecordHunter::new(HuntConfig {
+            filters: Vec::new(),
+            ..stopping_config(&path, stop)
+        })
+        .hunt();
+        assert_eq!(results.stop_reason, StopReason::IterationsReached);
+        assert_eq!(results.numbers_tested, 900);
+
+        let stop = StopConditions {
+            max_duration_secs: Some(0),
+            ..StopConditions::default()
+        };
+        let results = RecordHunter::new(stopping_config(&path, stop)).hunt();
+        assert_eq!(results.stop_reason, StopReason::MaxDuration);
+        assert_eq!(results.numbers_tested, 0);
+
+        // Records of 20 or more steps turn up among the 3-digit numbers
+        let stop = StopConditions {
+            max_records: Some(1),
+            ..StopConditions::default()
+        };
+        let results = RecordHunter::new(HuntConfig {
+            target_iterations: 20,
+            target_final_digits: 0,
+            filters: Vec::new(),
+            ..stopping_config(&path, stop)
+        })
+        .hunt();
+        // Record files are written to the working directory
+        for iterations in 20..=30 {
+            std::fs::remove_file(format!("record_{}_iter.json", iterations)).ok();
+        }
+        assert_eq!(results.stop_reason, StopReason::MaxRecords);
+        assert_eq!(results.numbers_tested, 900);
+
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(&checkpoint.thread_cache_file).ok();
+    }
+}
@@ -1,23 +1,54 @@
-use crate::lychrel::{lychrel_iteration, IterationResult};
-use crate::search_checkpoint::{SearchCheckpoint, SearchCheckpointBuilder};
+use crate::classification::classify_candidates;
+use crate::lychrel::{iterate, lychrel_iteration_radix, store_thread, IterationResult};
+use crate::number_source::resume_source_search;
+use crate::search_checkpoint::{
+    records_file, ChunkProgress, RecordLog, SearchCheckpoint, SearchCheckpointBuilder,
+};
+use crate::search_stats::SearchStatistics;
+use crate::search_stream::SearchSummary;
+use crate::thread_cache::{ThreadCache, ThreadInfo};
+use crate::AdaptiveNumber;
 use num_bigint::BigUint;
+use num_traits::ToPrimitive;
 use rayon::prelude::*;
-use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::{mpsc, Condvar, Mutex};
 use std::time::Instant;
 
+/// Largest number of consecutive values handed to one parallel task
+const MAX_CHUNK_SIZE: u64 = 10_000;
+
+/// Entries each parallel worker may add on top of the shared cache snapshot
+const WORKER_CACHE_SIZE: usize = 10_000;
+
 pub struct SearchConfig {
     pub start: BigUint,
     pub end: BigUint,
-    pub max_iterations: u32,
+    pub max_iterations: u64,
+    pub radix: u32,
+    pub classify: bool,
     pub parallel: bool,
     pub checkpoint_interval: Option<u64>,
     pub checkpoint_file: Option<String>,
+    /// Thread cache size in entries, `None` to test every number in full
+    pub cache_size: Option<usize>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SearchResults {
     pub total_tested: u64,
     pub potential_lychrel: Vec<IterationResult>,
     pub palindromes_found: Vec<IterationResult>,
+    pub palindrome_starts: u64,
+    /// Iteration histogram, most-delayed numbers and Lychrel counts per digit length
+    pub statistics: SearchStatistics,
+    /// Numbers answered from the thread cache before their iteration finished
+    #[serde(default)]
+    pub cache_hits: u64,
+    /// Numbers tested in full while a thread cache was in use
+    #[serde(default)]
+    pub cache_misses: u64,
 }
 
 impl Default for SearchResults {
@@ -32,10 +63,193 @@ impl SearchResults {
             total_tested: 0,
             potential_lychrel: Vec::new(),
             palindromes_found: Vec::new(),
+            palindrome_starts: 0,
+            statistics: SearchStatistics::new(),
+            cache_hits: 0,
+            cache_misses: 0,
+        }
+    }
+
+    /// Share of numbers answered from the thread cache, `None` if no cache was used
+    pub fn cache_hit_rate(&self) -> Option<f64> {
+        let total = self.cache_hits + self.cache_misses;
+        (total > 0).then(|| self.cache_hits as f64 / total as f64)
+    }
+
+    /// Test a single number, through the thread cache if there is one
+    pub(crate) fn test(
+        &mut self,
+        start: &BigUint,
+        max_iterations: u64,
+        radix: u32,
+        cache: Option<&mut ThreadCache>,
+    ) {
+        if let Some(start) = start.to_u128() {
+            return self.test_native(start, max_iterations, radix, cache);
+        }
+        let result = match cache {
+            Some(cache) => self.test_cached(start.clone(), max_iterations, radix, cache),
+            None => lychrel_iteration_radix(start.clone(), max_iterations, radix),
+        };
+        self.record(result, radix);
+    }
+
+    /// Test a number that fits in a machine word
+    ///
+    /// Palindrome starts are only counted, so a BigUint is built only for
+    /// the results that are kept.
+    pub(crate) fn test_native(
+        &mut self,
+        start: u128,
+        max_iterations: u64,
+        radix: u32,
+        cache: Option<&mut ThreadCache>,
+    ) {
+        let mut current = AdaptiveNumber::from_u128_radix(start, radix);
+        if current.is_palindrome() {
+            // Tested in full, like every number the cache does not answer
+            if cache.is_some() {
+                self.cache_misses += 1;
+            }
+            self.total_tested += 1;
+            self.palindrome_starts += 1;
+            return;
         }
+
+        let result = match cache {
+            Some(cache) => self.test_cached(BigUint::from(start), max_iterations, radix, cache),
+            None => {
+                let outcome = iterate(&mut current, max_iterations);
+                outcome.into_result(BigUint::from(start), &current)
+            }
+        };
+        self.record(result, radix);
+    }
+
+    /// Test a number through the thread cache, counting whether it was a hit
+    fn test_cached(
+        &mut self,
+        start: BigUint,
+        max_iterations: u64,
+        radix: u32,
+        cache: &mut ThreadCache,
+    ) -> IterationResult {
+        let (result, hit) = cached_iteration(start, max_iterations, radix, cache);
+        if hit {
+            self.cache_hits += 1;
+        } else {
+            self.cache_misses += 1;
+        }
+        result
+    }
+
+    /// Record a single tested number in the matching bucket
+    fn record(&mut self, result: IterationResult, radix: u32) {
+        self.total_tested += 1;
+        self.statistics.record(&result, radix);
+
+        if result.is_potential_lychrel {
+            self.potential_lychrel.push(result);
+        } else if result.iterations > 0 {
+            self.palindromes_found.push(result);
+        } else {
+            self.palindrome_starts += 1;
+        }
+    }
+
+    /// Counts and statistics tables of the results, without the records
+    pub fn summary(&self) -> SearchSummary {
+        SearchSummary {
+            total_tested: self.total_tested,
+            potential_lychrel: self.potential_lychrel.len() as u64,
+            palindromes_found: self.palindromes_found.len() as u64,
+            palindrome_starts: self.palindrome_starts,
+            statistics: self.statistics.clone(),
+            cache_hits: self.cache_hits,
+            cache_misses: self.cache_misses,
+        }
+    }
+
+    /// Add the results of another part of the same search
+    pub(crate) fn merge(&mut self, other: SearchResults) {
+        self.total_tested += other.total_tested;
+        self.potential_lychrel.extend(other.potential_lychrel);
+        self.palindromes_found.extend(other.palindromes_found);
+        self.palindrome_starts += other.palindrome_starts;
+        self.statistics.merge(other.statistics);
+        self.cache_hits += other.cache_hits;
+        self.cache_misses += other.cache_misses;
+    }
+
+    /// Put candidates and palindromes in ascending order of start number
+    ///
+    /// Parallel chunks finish in any order; the merged lists are runs of
+    /// sorted chunks, which a stable sort puts together cheaply.
+    pub(crate) fn sort(&mut self) {
+        self.potential_lychrel
+            .sort_by(|a, b| a.start_number.cmp(&b.start_number));
+        self.palindromes_found
+            .sort_by(|a, b| a.start_number.cmp(&b.start_number));
     }
 }
 
+/// Largest exponent `parse_bound` accepts, so a typo cannot ask for a huge power
+const MAX_BOUND_EXPONENT: u32 = 10_000;
+
+/// Parse a range bound given in decimal or in scientific notation
+///
+/// Accepts plain decimal integers of any size as well as forms like `1e25`
+/// or `2.5e24`, as long as the value is a whole number and the exponent is
+/// at most 10000.
+///
+/// # Arguments
+///
+/// * `s` - The text to parse
+///
+/// # Returns
+///
+/// The parsed number, or a message describing why the text is not a valid bound
+///
+/// # Examples
+///
+/// ```
+/// use lychrel_finder::search::parse_bound;
+/// use num_bigint::BigUint;
+///
+/// assert_eq!(parse_bound("1e3").unwrap(), BigUint::from(1000u32));
+/// assert_eq!(parse_bound("2.5e2").unwrap(), BigUint::from(250u32));
+/// assert!(parse_bound("1.5e0").is_err());
+/// ```
+pub fn parse_bound(s: &str) -> Result<BigUint, String> {
+    let s = s.trim();
+    let invalid = || format!("invalid number '{}'", s);
+
+    let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<u32>().map_err(|_| invalid())?),
+        None => (s, 0),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let fraction = fraction.trim_end_matches('0');
+
+    if whole.is_empty() || !(whole.chars().chain(fraction.chars())).all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    if exponent > MAX_BOUND_EXPONENT {
+        return Err(format!(
+            "exponent of '{}' is larger than {}",
+            s, MAX_BOUND_EXPONENT
+        ));
+    }
+    if fraction.len() > exponent as usize {
+        return Err(format!("'{}' is not a whole number", s));
+    }
+
+    let digits: BigUint = format!("{}{}", whole, fraction)
+        .parse()
+        .map_err(|_| invalid())?;
+    Ok(digits * BigUint::from(10u32).pow(exponent - fraction.len() as u32))
+}
+
 /// Search for Lychrel numbers in a specified range
 ///
 /// This function performs a search for potential Lychrel numbers in the given range,
@@ -49,9 +263,12 @@ impl SearchResults {
 ///   - `start`: Starting number of the range
 ///   - `end`: Ending number of the range
 ///   - `max_iterations`: Maximum iterations to test each number
+///   - `radix`: Base the reverse-add iteration is performed in (10 for decimal)
+///   - `classify`: Tag potential Lychrel numbers as seed or kin (keeps every seed thread in memory)
 ///   - `parallel`: Whether to use parallel processing
-///   - `checkpoint_interval`: Optional checkpoint frequency (sequential only)
-///   - `checkpoint_file`: Optional checkpoint file path (sequential only)
+///   - `checkpoint_interval`: Optional checkpoint frequency (parallel only here, `search_range_resumable` also checkpoints sequential runs)
+///   - `checkpoint_file`: Optional checkpoint file path
+///   - `cache_size`: Optional thread cache size; results are the same with or without it
 ///
 /// # Returns
 ///
@@ -59,6 +276,7 @@ impl SearchResults {
 /// - Total numbers tested
 /// - Vector of potential Lychrel numbers found
 /// - Vector of numbers that reached palindromes
+/// - Count of numbers that were palindromes from the start
 ///
 /// # Examples
 ///
@@ -70,20 +288,32 @@ impl SearchResults {
 ///     start: BigUint::from(1u32),
 ///     end: BigUint::from(100u32),
 ///     max_iterations: 1000,
+///     radix: 10,
+///     classify: false,
 ///     parallel: true,
 ///     checkpoint_interval: None,
 ///     checkpoint_file: None,
+///     cache_size: None,
 /// };
 ///
 /// let results = search_range(config);
 /// println!("Found {} potential Lychrel numbers", results.potential_lychrel.len());
 /// ```
 pub fn search_range(config: SearchConfig) -> SearchResults {
-    if config.parallel {
+    let classify = config.classify;
+    let max_iterations = config.max_iterations;
+    let radix = config.radix;
+
+    let mut results = if config.parallel {
         search_parallel(config)
     } else {
         search_sequential(config)
+    };
+
+    if classify {
+        classify_candidates(&mut results.potential_lychrel, max_iterations, radix);
     }
+    results
 }
 
 /// Search for Lychrel numbers in a range with resumable progress
@@ -91,6 +321,8 @@ pub fn search_range(config: SearchConfig) -> SearchResults {
 /// This function searches for potential Lychrel numbers in a specified range
 /// while providing periodic progress updates through a callback function.
 /// It supports checkpointing to allow resuming the search from where it left off.
+/// In parallel mode the range is processed in chunks and the checkpoint records
+/// which chunks are complete, so the callback reports once per finished chunk.
 ///
 /// # Arguments
 ///
@@ -111,9 +343,12 @@ pub fn search_range(config: SearchConfig) -> SearchResults {
 ///     start: BigUint::from(1u32),
 ///     end: BigUint::from(1000u32),
 ///     max_iterations: 100,
+///     radix: 10,
+///     classify: false,
 ///     parallel: false,
 ///     checkpoint_interval: Some(100),
 ///     checkpoint_file: Some("checkpoint.json".to_string()),
+///     cache_size: None,
 /// };
 ///
 /// let results = search_range_resumable(config, |tested, current, is_checkpoint| {
@@ -124,43 +359,78 @@ pub fn search_range_resumable<F>(config: SearchConfig, mut progress_callback: F)
 where
     F: FnMut(u64, &BigUint, bool),
 {
+    if config.parallel {
+        let tracker = ChunkTracker::new(
+            config.start.clone(),
+            parallel_chunk_size(&config.start, &config.end),
+        );
+        let mut results = search_parallel_tracked(
+            &config,
+            tracker,
+            SearchResults::new(),
+            None,
+            0.0,
+            progress_callback,
+        );
+        if config.classify {
+            classify_candidates(
+                &mut results.potential_lychrel,
+                config.max_iterations,
+                config.radix,
+            );
+        }
+        return results;
+    }
+
     let start_time = Instant::now();
     let mut results = SearchResults::new();
+    let mut cache = config.cache_size.map(ThreadCache::new);
     let mut current = config.start.clone();
     let mut last_checkpoint = 0u64;
+    let mut log = None;
 
     while current <= config.end {
-        let result = lychrel_iteration(current.clone(), config.max_iterations);
-        results.total_tested += 1;
-
-        if result.is_potential_lychrel {
-            results.potential_lychrel.push(result);
-        } else if result.iterations > 0 {
-            results.palindromes_found.push(result);
-        }
+        results.test(
+            &current,
+            config.max_iterations,
+            config.radix,
+            cache.as_mut(),
+        );
 
-        // Save checkpoint periodically
-        let should_save_checkpoint = if let Some(interval) = config.checkpoint_interval {
-            results.total_tested - last_checkpoint >= interval
-        } else {
-            false
-        };
+        // Save checkpoint periodically, and a last one on a stop request
+        let stop = config.checkpoint_file.is_some() && crate::interrupt::stop_requested();
+        let should_save_checkpoint = stop
+            || if let Some(interval) = config.checkpoint_interval {
+                results.total_tested - last_checkpoint >= interval
+            } else {
+                false
+            };
 
         if should_save_checkpoint {
             if let Some(ref file) = config.checkpoint_file {
-                let checkpoint = SearchCheckpointBuilder::new()
-                    .start_range(config.start.clone())
-                    .end_range(config.end.clone())
-                    .current_number(current.clone())
-                    .max_iterations(config.max_iterations)
-                    .numbers_tested(results.total_tested)
-                    .potential_lychrel(results.potential_lychrel.clone())
-                    .checkpoint_interval(config.checkpoint_interval)
-                    .checkpoint_file(config.checkpoint_file.clone())
-                    .elapsed_secs(start_time.elapsed().as_secs_f64())
-                    .build();
-
-                if let Err(e) = checkpoint.save(file) {
+                let saved = log
+                    .get_or_insert_with(|| RecordLog::new(records_file(file)))
+                    .append(&results)
+                    .and_then(|records| {
+                        SearchCheckpointBuilder::new()
+                            .start_range(config.start.clone())
+                            .end_range(config.end.clone())
+                            .current_number(current.clone())
+                            .max_iterations(config.max_iterations)
+                            .radix(config.radix)
+                            .classify(config.classify)
+                            .numbers_tested(results.total_tested)
+                            .summary(results.summary())
+                            .records(records)
+                            .checkpoint_interval(config.checkpoint_interval)
+                            .checkpoint_file(config.checkpoint_file.clone())
+                            .cache_size(config.cache_size)
+                            .elapsed_secs(start_time.elapsed().as_secs_f64())
+                            .build()
+                            .save(file)
+                    });
+
+                if let Err(e) = saved {
                     eprintln!("Warning: Failed to save checkpoint: {}", e);
                 } else {
                     progress_callback(results.total_tested, &current, true);
@@ -170,10 +440,20 @@ where
         } else {
             progress_callback(results.total_tested, &current, false);
         }
+        if stop {
+            break;
+        }
 
         current += 1u32;
     }
 
+    if config.classify {
+        classify_candidates(
+            &mut results.potential_lychrel,
+            config.max_iterations,
+            config.radix,
+        );
+    }
     results
 }
 
@@ -184,57 +464,96 @@ pub fn resume_search_from_checkpoint<F>(
 where
     F: FnMut(u64, &BigUint, bool),
 {
-    let start_time = Instant::now();
-    let mut results = SearchResults::new();
-    results.total_tested = checkpoint.numbers_tested;
+    if let Some(ref source) = checkpoint.source {
+        return resume_source_search(source, &checkpoint, progress_callback).unwrap_or_else(|e| {
+            eprintln!("Warning: Failed to read {}: {}", source, e);
+            results_from_checkpoint(&checkpoint).0
+        });
+    }
 
-    // Recreate potential_lychrel from saved numbers
-    for num in &checkpoint.potential_lychrel_found {
-        let result = IterationResult {
-            start_number: num.clone(),
-            is_palindrome: false,
-            iterations: checkpoint.max_iterations,
-            final_number: None,
-            is_potential_lychrel: true,
+    let (mut results, mut log) = results_from_checkpoint(&checkpoint);
+
+    if let Some(ref progress) = checkpoint.parallel {
+        let config = SearchConfig {
+            start: checkpoint.start_range.clone(),
+            end: checkpoint.end_range.clone(),
+            max_iterations: checkpoint.max_iterations,
+            radix: checkpoint.radix,
+            classify: checkpoint.classify,
+            parallel: true,
+            checkpoint_interval: checkpoint.checkpoint_interval,
+            checkpoint_file: checkpoint.checkpoint_file.clone(),
+            cache_size: checkpoint.cache_size,
         };
-        results.potential_lychrel.push(result);
+        let tracker = ChunkTracker::from_progress(progress);
+        results = search_parallel_tracked(
+            &config,
+            tracker,
+            results,
+            log,
+            checkpoint.elapsed_secs,
+            progress_callback,
+        );
+        if config.classify {
+            classify_candidates(
+                &mut results.potential_lychrel,
+                config.max_iterations,
+                config.radix,
+            );
+        }
+        return results;
     }
 
+    let start_time = Instant::now();
+    let mut cache = checkpoint.cache_size.map(ThreadCache::new);
+
     let mut current = checkpoint.current_number.clone() + 1u32;
     let mut last_checkpoint = checkpoint.numbers_tested;
 
     while current <= checkpoint.end_range {
-        let result = lychrel_iteration(current.clone(), checkpoint.max_iterations);
-        results.total_tested += 1;
-
-        if result.is_potential_lychrel {
-            results.potential_lychrel.push(result);
-        } else if result.iterations > 0 {
-            results.palindromes_found.push(result);
-        }
+        results.test(
+            &current,
+            checkpoint.max_iterations,
+            checkpoint.radix,
+            cache.as_mut(),
+        );
 
-        // Save checkpoint periodically
-        let should_save_checkpoint = if let Some(interval) = checkpoint.checkpoint_interval {
-            results.total_tested - last_checkpoint >= interval
-        } else {
-            false
-        };
+        // Save checkpoint periodically, and a last one on a stop request
+        let stop = checkpoint.checkpoint_file.is_some() && crate::interrupt::stop_requested();
+        let should_save_checkpoint = stop
+            || if let Some(interval) = checkpoint.checkpoint_interval {
+                results.total_tested - last_checkpoint >= interval
+            } else {
+                false
+            };
 
         if should_save_checkpoint {
             if let Some(ref file) = checkpoint.checkpoint_file {
-                let new_checkpoint = SearchCheckpointBuilder::new()
-                    .start_range(checkpoint.start_range.clone())
-                    .end_range(checkpoint.end_range.clone())
-                    .current_number(current.clone())
-                    .max_iterations(checkpoint.max_iterations)
-                    .numbers_tested(results.total_tested)
-                    .potential_lychrel(results.potential_lychrel.clone())
-                    .checkpoint_interval(checkpoint.checkpoint_interval)
-                    .checkpoint_file(checkpoint.checkpoint_file.clone())
-                    .elapsed_secs(checkpoint.elapsed_secs + start_time.elapsed().as_secs_f64())
-                    .build();
-
-                if let Err(e) = new_checkpoint.save(file) {
+                let saved = log
+                    .get_or_insert_with(|| RecordLog::new(records_file(file)))
+                    .append(&results)
+                    .and_then(|records| {
+                        SearchCheckpointBuilder::new()
+                            .start_range(checkpoint.start_range.clone())
+                            .end_range(checkpoint.end_range.clone())
+                            .current_number(current.clone())
+                            .max_iterations(checkpoint.max_iterations)
+                            .radix(checkpoint.radix)
+                            .classify(checkpoint.classify)
+                            .numbers_tested(results.total_tested)
+                            .summary(results.summary())
+                            .records(records)
+                            .checkpoint_interval(checkpoint.checkpoint_interval)
+                            .checkpoint_file(checkpoint.checkpoint_file.clone())
+                            .cache_size(checkpoint.cache_size)
+                            .elapsed_secs(
+                                checkpoint.elapsed_secs + start_time.elapsed().as_secs_f64(),
+                            )
+                            .build()
+                            .save(file)
+                    });
+
+                if let Err(e) = saved {
                     eprintln!("Warning: Failed to save checkpoint: {}", e);
                 } else {
                     progress_callback(results.total_tested, &current, true);
@@ -244,69 +563,591 @@ where
         } else {
             progress_callback(results.total_tested, &current, false);
         }
+        if stop {
+            break;
+        }
 
         current += 1u32;
     }
 
+    if checkpoint.classify {
+        classify_candidates(
+            &mut results.potential_lychrel,
+            checkpoint.max_iterations,
+            checkpoint.radix,
+        );
+    }
     results
 }
 
-fn search_sequential(config: SearchConfig) -> SearchResults {
+/// Rebuild the results stored in a search checkpoint and its records file
+///
+/// Checkpoints written before records files were kept only list the
+/// potential Lychrel numbers, so those come back without their final
+/// numbers and without the palindromes found before the checkpoint.
+///
+/// # Returns
+///
+/// The results, and the records file to go on appending to if there is one
+pub(crate) fn results_from_checkpoint(
+    checkpoint: &SearchCheckpoint,
+) -> (SearchResults, Option<RecordLog>) {
     let mut results = SearchResults::new();
-    let mut current = config.start.clone();
 
-    while current <= config.end {
-        let result = lychrel_iteration(current.clone(), config.max_iterations);
-        results.total_tested += 1;
+    if let Some(ref summary) = checkpoint.summary {
+        results.total_tested = summary.total_tested;
+        results.palindrome_starts = summary.palindrome_starts;
+        results.statistics = summary.statistics.clone();
+        results.cache_hits = summary.cache_hits;
+        results.cache_misses = summary.cache_misses;
 
-        if result.is_potential_lychrel {
-            results.potential_lychrel.push(result);
-        } else if result.iterations > 0 {
-            results.palindromes_found.push(result);
+        let Some(ref records) = checkpoint.records else {
+            return (results, None);
+        };
+        return match RecordLog::resume(records, &mut results) {
+            Ok(log) => (results, Some(log)),
+            Err(e) => {
+                eprintln!("Warning: Failed to read {}: {}", records.file, e);
+                results.potential_lychrel.clear();
+                results.palindromes_found.clear();
+                (results, None)
+            }
+        };
+    }
+
+    results.total_tested = checkpoint.numbers_tested;
+
+    // Recreate potential_lychrel from saved numbers
+    for num in &checkpoint.potential_lychrel_found {
+        let result = IterationResult {
+            start_number: num.clone(),
+            is_palindrome: false,
+            iterations: checkpoint.max_iterations,
+            final_number: None,
+            is_potential_lychrel: true,
+            classification: None,
+        };
+        results.potential_lychrel.push(result);
+    }
+    (results, None)
+}
+
+fn search_sequential(config: SearchConfig) -> SearchResults {
+    let mut cache = config.cache_size.map(ThreadCache::new);
+    search_chunk(
+        config.start,
+        &config.end,
+        config.max_iterations,
+        config.radix,
+        cache.as_mut(),
+    )
+}
+
+/// Lychrel iteration that stops as soon as the thread cache settles the outcome
+///
+/// Cached threads store every value with the number of iterations it is
+/// away from its seed, so a hit gives the exact remaining iteration count
+/// when the thread reached a palindrome. A thread that did not only settles
+/// the number if it was followed far enough to cover the remaining
+/// iterations; otherwise the iteration carries on as normal. A settled
+/// number returns at once with the same outcome and iteration count as
+/// `lychrel_iteration_radix`, but, like `lychrel_iteration_with_cache_radix`,
+/// with no final number, since that would take the skipped steps.
+///
+/// # Returns
+///
+/// The iteration result, and whether the cache provided it
+pub(crate) fn cached_iteration(
+    start: BigUint,
+    max_iterations: u64,
+    radix: u32,
+    cache: &mut ThreadCache,
+) -> (IterationResult, bool) {
+    let mut current = AdaptiveNumber::from_biguint_radix(&start, radix);
+    if current.is_palindrome() {
+        return (lychrel_iteration_radix(start, max_iterations, radix), false);
+    }
+
+    let mut iteration_count = 0;
+
+    while iteration_count < max_iterations {
+        if let Some(info) = cache.check_number(&current) {
+            // Some(Some(n)): palindrome after n iterations, Some(None): potential Lychrel
+            let settled = if info.reached_palindrome {
+                let remaining =
+                    info.palindrome_at_iteration.unwrap_or(u64::MAX) - info.iterations_from_seed;
+                let total = iteration_count.saturating_add(remaining);
+                Some((total <= max_iterations).then_some(total))
+            } else {
+                let remaining = info.max_iterations_tested - info.iterations_from_seed;
+                (iteration_count.saturating_add(remaining) >= max_iterations).then_some(None)
+            };
+
+            if let Some(palindrome_at) = settled {
+                let result = IterationResult {
+                    start_number: start,
+                    is_palindrome: palindrome_at.is_some(),
+                    iterations: palindrome_at.unwrap_or(max_iterations),
+                    final_number: None,
+                    is_potential_lychrel: palindrome_at.is_none(),
+                    classification: None,
+                };
+                return (result, true);
+            }
         }
 
-        current += 1u32;
+        let palindrome = current.reverse_add();
+        iteration_count += 1;
+
+        if palindrome {
+            if cache.should_cache(iteration_count) {
+                store_thread(
+                    cache,
+                    &start,
+                    radix,
+                    None,
+                    ThreadInfo {
+                        seed_number: start.to_string(),
+                        iterations_from_seed: 0,
+                        max_iterations_tested: iteration_count,
+                        final_digits: current.len(),
+                        reached_palindrome: true,
+                        palindrome_at_iteration: Some(iteration_count),
+                    },
+                );
+            }
+            let result = IterationResult {
+                start_number: start,
+                is_palindrome: true,
+                iterations: iteration_count,
+                final_number: Some(current.to_biguint()),
+                is_potential_lychrel: false,
+                classification: None,
+            };
+            return (result, false);
+        }
+    }
+
+    if cache.should_cache(iteration_count) {
+        store_thread(
+            cache,
+            &start,
+            radix,
+            None,
+            ThreadInfo {
+                seed_number: start.to_string(),
+                iterations_from_seed: 0,
+                max_iterations_tested: iteration_count,
+                final_digits: current.len(),
+                reached_palindrome: false,
+                palindrome_at_iteration: None,
+            },
+        );
+    }
+    let result = IterationResult {
+        start_number: start,
+        is_palindrome: false,
+        iterations: iteration_count,
+        final_number: Some(current.to_biguint()),
+        is_potential_lychrel: true,
+        classification: None,
+    };
+    (result, false)
+}
+
+/// Split `start..=end` into consecutive inclusive chunks of at most `size` numbers
+pub(crate) fn range_chunks(
+    start: &BigUint,
+    end: &BigUint,
+    size: u64,
+) -> impl Iterator<Item = (BigUint, BigUint)> + Send {
+    let mut next = start.clone();
+    let end = end.clone();
+
+    std::iter::from_fn(move || {
+        if next > end {
+            return None;
+        }
+        let chunk_end = (&next + size - 1u32).min(end.clone());
+        let chunk_start = std::mem::replace(&mut next, &chunk_end + 1u32);
+        Some((chunk_start, chunk_end))
+    })
+}
+
+/// Chunk size giving every thread several chunks, capped to keep them balanced
+pub(crate) fn parallel_chunk_size(start: &BigUint, end: &BigUint) -> u64 {
+    if start > end {
+        return 1;
+    }
+    let span = end - start + 1u32;
+    let per_thread = span / (rayon::current_num_threads() as u64 * 8);
+    per_thread
+        .to_u64()
+        .unwrap_or(u64::MAX)
+        .clamp(1, MAX_CHUNK_SIZE)
+}
+
+/// Completed chunks of a parallel search, as a low-water mark plus the
+/// chunks that finished ahead of it
+struct ChunkTracker {
+    chunk_size: u64,
+    next_chunk: BigUint,
+    completed: BTreeSet<BigUint>,
+}
+
+impl ChunkTracker {
+    fn new(start: BigUint, chunk_size: u64) -> Self {
+        ChunkTracker {
+            chunk_size,
+            next_chunk: start,
+            completed: BTreeSet::new(),
+        }
+    }
+
+    fn from_progress(progress: &ChunkProgress) -> Self {
+        ChunkTracker {
+            chunk_size: progress.chunk_size.max(1),
+            next_chunk: progress.next_chunk.clone(),
+            completed: progress.completed_chunks.iter().cloned().collect(),
+        }
+    }
+
+    /// Mark a chunk as done and advance the low-water mark past any run of
+    /// completed chunks
+    fn complete(&mut self, chunk_start: BigUint) {
+        self.completed.insert(chunk_start);
+        while self.completed.remove(&self.next_chunk) {
+            self.next_chunk += self.chunk_size;
+        }
+    }
+
+    fn progress(&self) -> ChunkProgress {
+        ChunkProgress {
+            chunk_size: self.chunk_size,
+            next_chunk: self.next_chunk.clone(),
+            completed_chunks: self.completed.iter().cloned().collect(),
+        }
+    }
+}
+
+fn search_chunk(
+    start: BigUint,
+    end: &BigUint,
+    max_iterations: u64,
+    radix: u32,
+    mut cache: Option<&mut ThreadCache>,
+) -> SearchResults {
+    let mut results = SearchResults::new();
+
+    // Count on a machine word while the range fits in one
+    if let (Some(start), Some(end)) = (start.to_u128(), end.to_u128()) {
+        for n in start..=end {
+            results.test_native(n, max_iterations, radix, cache.as_deref_mut());
+        }
+        return results;
     }
 
+    let mut current = start;
+    while current <= *end {
+        results.test(&current, max_iterations, radix, cache.as_deref_mut());
+        current += 1u32;
+    }
     results
 }
 
-fn search_parallel(config: SearchConfig) -> SearchResults {
-    let start_u64 = config.start.to_string().parse::<u64>().unwrap_or(0);
-    let end_u64 = config.end.to_string().parse::<u64>().unwrap_or(start_u64);
+/// Chunks a worker may start past the lowest one not yet handed to `done`
+///
+/// Keeps a slow `done` from letting finished chunks pile up in memory.
+struct ChunkWindow {
+    state: Mutex<WindowState>,
+    moved: Condvar,
+    ahead: usize,
+}
+
+#[derive(Default)]
+struct WindowState {
+    /// Lowest chunk index not yet handed to `done`
+    lowest: usize,
+    /// Indices above `lowest` that were
+    finished: BTreeSet<usize>,
+    /// Workers no longer wait
+    closed: bool,
+}
 
-    let potential_lychrel = Arc::new(Mutex::new(Vec::new()));
-    let palindromes = Arc::new(Mutex::new(Vec::new()));
+impl ChunkWindow {
+    fn new(ahead: usize) -> Self {
+        ChunkWindow {
+            state: Mutex::new(WindowState::default()),
+            moved: Condvar::new(),
+            ahead,
+        }
+    }
 
-    let total_tested = if end_u64 >= start_u64 {
-        end_u64 - start_u64 + 1
-    } else {
-        0
+    /// Block until chunk `index` is close enough to the lowest unfinished one
+    fn wait(&self, index: usize) {
+        let mut state = self.state.lock().unwrap();
+        while !state.closed && index >= state.lowest + self.ahead {
+            state = self.moved.wait(state).unwrap();
+        }
+    }
+
+    fn finish(&self, index: usize) {
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+        state.finished.insert(index);
+        while state.finished.remove(&state.lowest) {
+            state.lowest += 1;
+        }
+        self.moved.notify_all();
+    }
+
+    /// Let every waiting worker go, so they can end if `done` panicked
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.moved.notify_all();
+    }
+}
+
+/// Run `work` on every chunk on the rayon pool, handing each finished chunk
+/// to `done` on the calling thread
+///
+/// A chunk is any unit of work: range searches pass `(start, end)` pairs,
+/// source searches slices of a batch of numbers.
+///
+/// Without a cache all chunks are bridged onto the pool in one go. With a
+/// cache they run in waves, like the hunter's batches: workers read a
+/// snapshot of the shared cache and fill worker caches of their own, which
+/// are merged back into the shared cache once the wave is done.
+///
+/// Finished chunks go through a channel of a few per thread, and workers
+/// start no chunk more than four per thread past the lowest one `done` has
+/// not seen yet, so a slow `done` holds the workers back instead of letting
+/// finished chunks pile up.
+pub(crate) fn run_chunks<I, C, T, W, D>(
+    chunks: I,
+    cache: &mut Option<ThreadCache>,
+    work: W,
+    mut done: D,
+) where
+    I: Iterator<Item = C> + Send,
+    C: Send,
+    T: Send,
+    W: Fn(&C, Option<&mut ThreadCache>) -> T + Sync,
+    D: FnMut(C, T),
+{
+    let threads = rayon::current_num_threads();
+    let wave_size = match cache {
+        Some(_) => threads * 4,
+        None => usize::MAX,
     };
+    let mut chunks = chunks.enumerate();
+    let window = ChunkWindow::new(threads * 4);
 
-    (start_u64..=end_u64).into_par_iter().for_each(|n| {
-        let result = lychrel_iteration(BigUint::from(n), config.max_iterations);
+    loop {
+        let snapshot = cache.as_mut().map(|c| c.take_snapshot());
+        let worker_snapshot = snapshot.clone();
+        let wave = chunks.by_ref().take(wave_size);
+        let (work, window) = (&work, &window);
+        let (sender, receiver) = mpsc::sync_channel(threads * 2);
+        let mut worker_caches = Vec::new();
 
-        if result.is_potential_lychrel {
-            potential_lychrel.lock().unwrap().push(result);
-        } else if result.iterations > 0 {
-            palindromes.lock().unwrap().push(result);
+        std::thread::scope(|scope| {
+            scope.spawn(move || {
+                wave.par_bridge()
+                    .for_each_with(sender, |sender, (index, chunk)| {
+                        window.wait(index);
+                        let mut worker = worker_snapshot
+                            .clone()
+                            .map(|s| ThreadCache::new_worker(s, WORKER_CACHE_SIZE));
+                        let output = work(&chunk, worker.as_mut());
+                        // The receiver only goes away if the calling thread panicked
+                        let _ = sender.send((index, chunk, output, worker));
+                    });
+            });
+
+            // Workers would wait forever on a window `done` no longer moves
+            struct CloseOnDrop<'a>(&'a ChunkWindow);
+            impl Drop for CloseOnDrop<'_> {
+                fn drop(&mut self) {
+                    if std::thread::panicking() {
+                        self.0.close();
+                    }
+                }
+            }
+            let _guard = CloseOnDrop(window);
+
+            for (index, chunk, output, worker) in receiver {
+                worker_caches.extend(worker);
+                done(chunk, output);
+                window.finish(index);
+            }
+        });
+
+        let (Some(cache), Some(snapshot)) = (cache.as_mut(), snapshot) else {
+            break;
+        };
+        if worker_caches.is_empty() {
+            break;
         }
-    });
 
-    SearchResults {
-        total_tested,
-        potential_lychrel: Arc::try_unwrap(potential_lychrel)
-            .unwrap()
-            .into_inner()
-            .unwrap(),
-        palindromes_found: Arc::try_unwrap(palindromes).unwrap().into_inner().unwrap(),
+        // Combine the worker caches first, so the shared cache is the only
+        // holder of its map again when they are merged into it
+        let merged = worker_caches.into_iter().fold(
+            ThreadCache::new_empty(WORKER_CACHE_SIZE),
+            |mut a, b| {
+                a.merge(b);
+                a
+            },
+        );
+        cache.restore_snapshot(snapshot);
+        cache.merge(merged);
     }
 }
 
+fn search_parallel(config: SearchConfig) -> SearchResults {
+    let tracker = ChunkTracker::new(
+        config.start.clone(),
+        parallel_chunk_size(&config.start, &config.end),
+    );
+    search_parallel_tracked(
+        &config,
+        tracker,
+        SearchResults::new(),
+        None,
+        0.0,
+        |_, _, _| {},
+    )
+}
+
+/// Run the chunks the tracker has not completed yet on the rayon pool
+///
+/// Each worker collects a whole chunk into its own `SearchResults` and hands
+/// it back to the calling thread, which merges it, advances the tracker,
+/// writes checkpoints and reports progress, so no lock is taken per number
+/// and the callback does not need to be thread-safe. The merged results are
+/// sorted at the end, so every run over the same range gives the same output.
+fn search_parallel_tracked<F>(
+    config: &SearchConfig,
+    mut tracker: ChunkTracker,
+    mut results: SearchResults,
+    mut log: Option<RecordLog>,
+    base_elapsed: f64,
+    mut progress_callback: F,
+) -> SearchResults
+where
+    F: FnMut(u64, &BigUint, bool),
+{
+    let start_time = Instant::now();
+    let mut last_checkpoint = results.total_tested;
+    let (max_iterations, radix) = (config.max_iterations, config.radix);
+    let mut cache = config.cache_size.map(ThreadCache::new);
+
+    // With a checkpoint to save, a stop request starts no new chunk;
+    // chunks already running finish
+    let stoppable = config.checkpoint_file.is_some();
+    let skip = tracker.completed.clone();
+    let chunks = range_chunks(&tracker.next_chunk, &config.end, tracker.chunk_size)
+        .filter(move |(chunk_start, _)| !skip.contains(chunk_start))
+        .take_while(move |_| !(stoppable && crate::interrupt::stop_requested()));
+
+    run_chunks(
+        chunks,
+        &mut cache,
+        |(chunk_start, chunk_end), cache| {
+            search_chunk(chunk_start.clone(), chunk_end, max_iterations, radix, cache)
+        },
+        |(chunk_start, chunk_end), chunk_results| {
+            results.merge(chunk_results);
+            tracker.complete(chunk_start);
+
+            let should_save_checkpoint = if let Some(interval) = config.checkpoint_interval {
+                results.total_tested - last_checkpoint >= interval
+            } else {
+                false
+            };
+
+            if should_save_checkpoint {
+                if let Some(ref file) = config.checkpoint_file {
+                    let elapsed = base_elapsed + start_time.elapsed().as_secs_f64();
+                    let saved = save_parallel_checkpoint(
+                        config, file, &tracker, &results, &mut log, elapsed,
+                    );
+
+                    if let Err(e) = saved {
+                        eprintln!("Warning: Failed to save checkpoint: {}", e);
+                    } else {
+                        progress_callback(results.total_tested, &chunk_end, true);
+                        last_checkpoint = results.total_tested;
+                        return;
+                    }
+                }
+            }
+            progress_callback(results.total_tested, &chunk_end, false);
+        },
+    );
+
+    if crate::interrupt::stop_requested() {
+        if let Some(ref file) = config.checkpoint_file {
+            let elapsed = base_elapsed + start_time.elapsed().as_secs_f64();
+            match save_parallel_checkpoint(config, file, &tracker, &results, &mut log, elapsed) {
+                Err(e) => eprintln!("Warning: Failed to save checkpoint: {}", e),
+                Ok(current) => progress_callback(results.total_tested, &current, true),
+            }
+        }
+    }
+
+    results.sort();
+    results
+}
+
+/// Save a checkpoint of a parallel search with the tracker's completed chunks
+///
+/// # Returns
+///
+/// The last number of the contiguous completed prefix, which the checkpoint
+/// records as its current number
+fn save_parallel_checkpoint(
+    config: &SearchConfig,
+    file: &str,
+    tracker: &ChunkTracker,
+    results: &SearchResults,
+    log: &mut Option<RecordLog>,
+    elapsed_secs: f64,
+) -> std::io::Result<BigUint> {
+    let records = log
+        .get_or_insert_with(|| RecordLog::new(records_file(file)))
+        .append(results)?;
+
+    // Last number of the contiguous completed prefix
+    let current_number = if tracker.next_chunk > config.start {
+        (&tracker.next_chunk - 1u32).min(config.end.clone())
+    } else {
+        config.start.clone()
+    };
+    SearchCheckpointBuilder::new()
+        .start_range(config.start.clone())
+        .end_range(config.end.clone())
+        .current_number(current_number.clone())
+        .max_iterations(config.max_iterations)
+        .radix(config.radix)
+        .classify(config.classify)
+        .numbers_tested(results.total_tested)
+        .summary(results.summary())
+        .records(records)
+        .checkpoint_interval(config.checkpoint_interval)
+        .checkpoint_file(config.checkpoint_file.clone())
+        .cache_size(config.cache_size)
+        .elapsed_secs(elapsed_secs)
+        .parallel(tracker.progress())
+        .build()
+        .save(file)?;
+    Ok(current_number)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::classification::Classification;
 
     #[test]
     fn test_search_small_range() {
@@ -314,9 +1155,12 @@ mod tests {
             start: BigUint::from(1u32),
             end: BigUint::from(10u32),
             max_iterations: 100,
+            radix: 10,
+            classify: false,
             parallel: false,
             checkpoint_interval: None,
             checkpoint_file: None,
+            cache_size: None,
         };
 
         let results = search_range(config);
@@ -329,9 +1173,12 @@ mod tests {
             start: BigUint::from(196u32),
             end: BigUint::from(196u32),
             max_iterations: 50,
+            radix: 10,
+            classify: false,
             parallel: false,
             checkpoint_interval: None,
             checkpoint_file: None,
+            cache_size: None,
         };
 
         let results = search_range(config);
@@ -341,4 +1188,429 @@ mod tests {
             BigUint::from(196u32)
         );
     }
+
+    #[test]
+    fn test_search_base_2() {
+        // 22 (10110 in base 2) is the smallest base 2 Lychrel number
+        let config = SearchConfig {
+            start: BigUint::from(1u32),
+            end: BigUint::from(22u32),
+            max_iterations: 500,
+            radix: 2,
+            classify: false,
+            parallel: false,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            cache_size: None,
+        };
+
+        let results = search_range(config);
+        assert_eq!(results.potential_lychrel.len(), 1);
+        assert_eq!(
+            results.potential_lychrel[0].start_number,
+            BigUint::from(22u32)
+        );
+    }
+
+    #[test]
+    fn test_search_classifies_candidates() {
+        for parallel in [false, true] {
+            let config = SearchConfig {
+                start: BigUint::from(1u32),
+                end: BigUint::from(1000u32),
+                max_iterations: 500,
+                radix: 10,
+                classify: true,
+                parallel,
+                checkpoint_interval: None,
+                checkpoint_file: None,
+                cache_size: None,
+            };
+
+            let results = search_range(config);
+            let seeds: Vec<String> = results
+                .potential_lychrel
+                .iter()
+                .filter(|r| r.classification == Some(Classification::Seed))
+                .map(|r| r.start_number.to_string())
+                .collect();
+            assert_eq!(seeds, vec!["196", "879"]);
+            assert_eq!(results.potential_lychrel.len(), 13);
+            // 1-9, 11, 22, ..., 99 and 101, 111, ..., 999
+            assert_eq!(results.palindrome_starts, 108);
+        }
+    }
+
+    #[test]
+    fn test_parse_bound() {
+        assert_eq!(parse_bound("196").unwrap(), BigUint::from(196u32));
+        assert_eq!(
+            parse_bound("1e25").unwrap().to_string(),
+            format!("1{}", "0".repeat(25))
+        );
+        assert_eq!(
+            parse_bound("123456789012345678901234567890")
+                .unwrap()
+                .to_string(),
+            "123456789012345678901234567890"
+        );
+        assert_eq!(parse_bound("1.50E2").unwrap(), BigUint::from(150u32));
+        assert_eq!(parse_bound("1e10000").unwrap().to_string().len(), 10_001);
+        for bad in [
+            "",
+            "e5",
+            "1e",
+            "-5",
+            "1.5",
+            "1e-3",
+            "12a",
+            "1e10001",
+            "1e4000000000",
+        ] {
+            assert!(parse_bound(bad).is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn test_parallel_search_beyond_u64() {
+        let start = parse_bound("1e25").unwrap();
+        let config = SearchConfig {
+            start: start.clone(),
+            end: &start + 999u32,
+            max_iterations: 100,
+            radix: 10,
+            classify: false,
+            parallel: true,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            cache_size: None,
+        };
+
+        let parallel = search_range(config);
+        let sequential = search_sequential(SearchConfig {
+            start: start.clone(),
+            end: &start + 999u32,
+            max_iterations: 100,
+            radix: 10,
+            classify: false,
+            parallel: false,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            cache_size: None,
+        });
+
+        assert_eq!(parallel.total_tested, 1000);
+        assert_eq!(parallel.palindrome_starts, sequential.palindrome_starts);
+        assert_eq!(
+            parallel.potential_lychrel.len(),
+            sequential.potential_lychrel.len()
+        );
+        assert!(parallel
+            .potential_lychrel
+            .iter()
+            .chain(&parallel.palindromes_found)
+            .all(|r| r.start_number >= start));
+    }
+
+    #[test]
+    fn test_parallel_search_is_deterministic() {
+        let config = |parallel| SearchConfig {
+            start: BigUint::from(1u32),
+            end: BigUint::from(20000u32),
+            max_iterations: 200,
+            radix: 10,
+            classify: false,
+            parallel,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            cache_size: None,
+        };
+        let sequential = search_range(config(false));
+
+        for _ in 0..2 {
+            let parallel = search_range(config(true));
+            assert_eq!(parallel, sequential);
+        }
+    }
+
+    #[test]
+    fn test_resume_parallel_checkpoint_with_gap() {
+        let config = |parallel| SearchConfig {
+            start: BigUint::from(1u32),
+            end: BigUint::from(1000u32),
+            max_iterations: 200,
+            radix: 10,
+            classify: false,
+            parallel,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            cache_size: None,
+        };
+        let full = search_sequential(config(false));
+
+        // Chunks 1-100 and 201-300 done, 101-200 still running when saved
+        let mut done = search_chunk(BigUint::from(1u32), &BigUint::from(100u32), 200, 10, None);
+        done.merge(search_chunk(
+            BigUint::from(201u32),
+            &BigUint::from(300u32),
+            200,
+            10,
+            None,
+        ));
+        let checkpoint = SearchCheckpointBuilder::new()
+            .start_range(BigUint::from(1u32))
+            .end_range(BigUint::from(1000u32))
+            .current_number(BigUint::from(100u32))
+            .max_iterations(200)
+            .radix(10)
+            .numbers_tested(done.total_tested)
+            .potential_lychrel(done.potential_lychrel)
+            .parallel(ChunkProgress {
+                chunk_size: 100,
+                next_chunk: BigUint::from(101u32),
+                completed_chunks: vec![BigUint::from(201u32)],
+            })
+            .build();
+
+        let mut calls = 0;
+        let resumed = resume_search_from_checkpoint(checkpoint, |_, _, _| calls += 1);
+        let found: Vec<_> = resumed
+            .potential_lychrel
+            .iter()
+            .map(|r| r.start_number.clone())
+            .collect();
+        let expected: Vec<_> = full
+            .potential_lychrel
+            .iter()
+            .map(|r| r.start_number.clone())
+            .collect();
+
+        assert_eq!(resumed.total_tested, 1000);
+        assert_eq!(found, expected);
+        // 101-200 and 301-1000 are left, in chunks of 100
+        assert_eq!(calls, 8);
+    }
+
+    #[test]
+    fn test_resumed_search_matches_uninterrupted() {
+        let path = std::env::temp_dir().join("lychrel_lossless_search_checkpoint.json");
+        let path = path.to_str().unwrap().to_string();
+        let full = search_sequential(SearchConfig {
+            start: BigUint::from(1u32),
+            end: BigUint::from(2000u32),
+            max_iterations: 200,
+            radix: 10,
+            classify: false,
+            parallel: false,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            cache_size: None,
+        });
+
+        let done = search_chunk(BigUint::from(1u32), &BigUint::from(1000u32), 200, 10, None);
+        let records_path = records_file(&path);
+        let records = RecordLog::new(records_path.clone()).append(&done).unwrap();
+        SearchCheckpointBuilder::new()
+            .start_range(BigUint::from(1u32))
+            .end_range(BigUint::from(2000u32))
+            .current_number(BigUint::from(1000u32))
+            .max_iterations(200)
+            .radix(10)
+            .numbers_tested(done.total_tested)
+            .summary(done.summary())
+            .records(records.clone())
+            .build()
+            .save(&path)
+            .unwrap();
+        let checkpoint = SearchCheckpoint::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(checkpoint.potential_lychrel_found.is_empty());
+        assert_eq!(
+            checkpoint.potential_lychrel_count(),
+            done.potential_lychrel.len() as u64
+        );
+
+        // Results a run appended after its last checkpoint are dropped
+        let mut records_text = std::fs::read_to_string(&records_path).unwrap();
+        records_text.push_str(&records_text.clone());
+        std::fs::write(&records_path, records_text).unwrap();
+
+        let resumed = resume_search_from_checkpoint(checkpoint, |_, _, _| {});
+        let records_len = std::fs::metadata(&records_path).unwrap().len();
+        std::fs::remove_file(&records_path).ok();
+        assert_eq!(records_len, records.bytes);
+        assert_eq!(resumed, full);
+    }
+
+    #[test]
+    fn test_parallel_search_writes_chunk_checkpoints() {
+        let path = std::env::temp_dir().join("lychrel_parallel_search_checkpoint.json");
+        let path = path.to_str().unwrap().to_string();
+        let config = SearchConfig {
+            start: BigUint::from(1u32),
+            end: BigUint::from(5000u32),
+            max_iterations: 200,
+            radix: 10,
+            classify: false,
+            parallel: true,
+            checkpoint_interval: Some(500),
+            checkpoint_file: Some(path.clone()),
+            cache_size: None,
+        };
+
+        let results = search_range(config);
+        let checkpoint = SearchCheckpoint::load(&path).unwrap();
+
+        // Resuming from whichever checkpoint was saved last gives the same results
+        let resumed = resume_search_from_checkpoint(checkpoint.clone(), |_, _, _| {});
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(records_file(&path)).ok();
+        assert_eq!(resumed, results);
+
+        assert_eq!(results.total_tested, 5000);
+        let progress = checkpoint.parallel.expect("parallel checkpoint");
+        assert!(checkpoint.numbers_tested >= 500);
+
+        // The saved count is exactly the prefix below the mark plus the listed chunks
+        let end = BigUint::from(5000u32);
+        let mut covered = &progress.next_chunk - 1u32;
+        for start in &progress.completed_chunks {
+            assert!(*start > progress.next_chunk);
+            covered += (start + progress.chunk_size - 1u32).min(end.clone()) - start + 1u32;
+        }
+        assert_eq!(covered, BigUint::from(checkpoint.numbers_tested));
+    }
+
+    #[test]
+    fn test_chunk_window_follows_lowest_unfinished_chunk() {
+        let window = ChunkWindow::new(2);
+        window.wait(1);
+
+        // Chunk 2 may only start once chunk 0 is done, whatever else is
+        window.finish(1);
+        assert_eq!(window.state.lock().unwrap().lowest, 0);
+        window.finish(0);
+        assert_eq!(window.state.lock().unwrap().lowest, 2);
+        window.wait(3);
+
+        // A closed window lets everything through
+        window.close();
+        window.wait(100);
+    }
+
+    #[test]
+    fn test_native_loop_matches_full_results() {
+        let around_max = BigUint::from(u128::MAX) - 2u32;
+        for (start, count, radix) in [
+            (BigUint::from(1u32), 3000u32, 10),
+            (BigUint::from(1u32), 3000, 2),
+            (around_max, 5, 10),
+        ] {
+            let end = &start + count - 1u32;
+            let mut expected = SearchResults::new();
+            let mut current = start.clone();
+            while current <= end {
+                expected.record(lychrel_iteration_radix(current.clone(), 60, radix), radix);
+                current += 1u32;
+            }
+            assert_eq!(search_chunk(start, &end, 60, radix, None), expected);
+        }
+    }
+
+    #[test]
+    fn test_cached_search_matches_uncached() {
+        let config = |parallel, cache_size| SearchConfig {
+            start: BigUint::from(1u32),
+            end: BigUint::from(12000u32),
+            max_iterations: 300,
+            radix: 10,
+            classify: false,
+            parallel,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            cache_size,
+        };
+        let plain = search_range(config(false, None));
+        assert_eq!(plain.cache_hit_rate(), None);
+
+        // Everything but the cache counters is the same, except that cache
+        // hits have no final number
+        for parallel in [false, true] {
+            let cached = search_range(config(parallel, Some(100_000)));
+            assert_eq!(cached.cache_hits + cached.cache_misses, 12000);
+            assert!(cached.cache_hit_rate().unwrap() > 0.0);
+
+            let mut expected = plain.clone();
+            let pairs = expected
+                .potential_lychrel
+                .iter_mut()
+                .zip(&cached.potential_lychrel)
+                .chain(
+                    expected
+                        .palindromes_found
+                        .iter_mut()
+                        .zip(&cached.palindromes_found),
+                );
+            let mut without_final = 0;
+            for (plain, cached) in pairs {
+                if cached.final_number.is_none() {
+                    plain.final_number = None;
+                    without_final += 1;
+                }
+            }
+            assert_eq!(without_final, cached.cache_hits);
+            assert_eq!(
+                SearchResults {
+                    cache_hits: 0,
+                    cache_misses: 0,
+                    ..cached
+                },
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_cache_shared_between_writers() {
+        use crate::lychrel::lychrel_iteration_with_cache_radix;
+
+        // 10911 reaches a palindrome after 55 steps, and 11901 joins its
+        // thread after one
+        let expected = lychrel_iteration_radix(BigUint::from(11901u32), 100, 10);
+        assert_eq!(expected.iterations, 55);
+
+        let mut cache = ThreadCache::new(1000);
+        lychrel_iteration_with_cache_radix(BigUint::from(10911u32), 100, 10, &mut cache, None);
+        let (result, hit) = cached_iteration(BigUint::from(11901u32), 100, 10, &mut cache);
+        assert!(hit);
+        assert_eq!(result.iterations, expected.iterations);
+
+        let mut cache = ThreadCache::new(1000);
+        cached_iteration(BigUint::from(10911u32), 100, 10, &mut cache);
+        let result =
+            lychrel_iteration_with_cache_radix(BigUint::from(11901u32), 100, 10, &mut cache, None);
+        assert_eq!(cache.stats().hits, 1);
+        assert_eq!(result.iterations, expected.iterations);
+        assert!(result.is_palindrome);
+    }
+
+    #[test]
+    fn test_cache_hit_skips_remaining_steps() {
+        let steps = || crate::adaptive_number::REVERSE_ADDS.with(|steps| steps.get());
+        let mut cache = ThreadCache::new(1000);
+        cached_iteration(BigUint::from(196u32), 300, 10, &mut cache);
+
+        // 295 + 592 = 887 = 196 + 691, so 295 joins the thread of 196 at once
+        let before = steps();
+        let (result, hit) = cached_iteration(BigUint::from(295u32), 300, 10, &mut cache);
+        assert!(hit);
+        assert_eq!(steps() - before, 1);
+        assert_eq!(result.iterations, 300);
+        assert!(result.is_potential_lychrel);
+        assert_eq!(result.final_number, None);
+
+        let before = steps();
+        lychrel_iteration_radix(BigUint::from(295u32), 300, 10);
+        assert_eq!(steps() - before, 300);
+    }
 }
@@ -1,19 +1,191 @@
 use crate::lychrel::IterationResult;
+use crate::number_source::NumberSource;
+use crate::search::SearchResults;
+use crate::search_stream::SearchSummary;
 use num_bigint::BigUint;
+use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+
+/// Completed chunks of a parallel search
+///
+/// Chunks are `chunk_size` consecutive numbers counted from `next_chunk`.
+/// Everything below `next_chunk` has been tested, and so has every chunk
+/// whose start is listed in `completed_chunks`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkProgress {
+    pub chunk_size: u64,
+    /// Start of the lowest chunk not yet completed
+    pub next_chunk: BigUint,
+    /// Starts of chunks above `next_chunk` that finished out of order
+    pub completed_chunks: Vec<BigUint>,
+}
+
+/// Progress of a search over a number source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceProgress {
+    /// Numbers taken from the source, which a resumed search skips
+    pub items_done: u64,
+    /// Whether the numbers were tested in parallel batches
+    pub parallel: bool,
+    /// Hash of the numbers taken from a file or stdin, so a resumed search
+    /// can tell it was given different input
+    #[serde(default)]
+    pub input_hash: Option<u64>,
+}
+
+/// Where a checkpointed search keeps its individual results
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordsProgress {
+    /// File the kept results are appended to, one JSON object per line
+    pub file: String,
+    /// Length of the file when the checkpoint was saved; a resumed search
+    /// drops anything written after it
+    pub bytes: u64,
+}
+
+/// Path of the records file kept next to a search checkpoint
+pub fn records_file(checkpoint_file: &str) -> String {
+    format!("{}_records.ndjson", checkpoint_file)
+}
+
+/// Append-only file of the candidates and palindromes of a checkpointed search
+///
+/// Each checkpoint appends only the results found since the one before, so
+/// saving a checkpoint costs the same however many results came before it.
+#[derive(Debug)]
+pub(crate) struct RecordLog {
+    file: String,
+    bytes: u64,
+    /// Entries of `potential_lychrel` and `palindromes_found` already written
+    written: (usize, usize),
+}
+
+impl RecordLog {
+    /// Start an empty log; whatever is at `file` is replaced on the first append
+    pub fn new(file: String) -> Self {
+        RecordLog {
+            file,
+            bytes: 0,
+            written: (0, 0),
+        }
+    }
+
+    /// Reopen the log of a checkpoint, cut back to its saved length
+    ///
+    /// The results in the log are added to `results`, which must not hold
+    /// any candidates or palindromes yet.
+    pub fn resume(progress: &RecordsProgress, results: &mut SearchResults) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&progress.file)?;
+        if file.metadata()?.len() < progress.bytes {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "records file is shorter than the checkpoint says",
+            ));
+        }
+        file.set_len(progress.bytes)?;
+
+        for line in BufReader::new(file).lines() {
+            let result: IterationResult = serde_json::from_str(&line?)?;
+            if result.is_potential_lychrel {
+                results.potential_lychrel.push(result);
+            } else {
+                results.palindromes_found.push(result);
+            }
+        }
+        Ok(RecordLog {
+            file: progress.file.clone(),
+            bytes: progress.bytes,
+            written: (
+                results.potential_lychrel.len(),
+                results.palindromes_found.len(),
+            ),
+        })
+    }
+
+    /// Append the results not written yet
+    ///
+    /// # Returns
+    ///
+    /// The position to store in the checkpoint
+    pub fn append(&mut self, results: &SearchResults) -> io::Result<RecordsProgress> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.file)?;
+        // Drops anything a run that stopped without a checkpoint left behind
+        file.set_len(self.bytes)?;
+        file.seek(SeekFrom::End(0))?;
+
+        let new_results = results.potential_lychrel[self.written.0..]
+            .iter()
+            .chain(&results.palindromes_found[self.written.1..]);
+        let mut writer = BufWriter::new(&mut file);
+        for result in new_results {
+            serde_json::to_writer(&mut writer, result)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        drop(writer);
+
+        self.bytes = file.stream_position()?;
+        self.written = (
+            results.potential_lychrel.len(),
+            results.palindromes_found.len(),
+        );
+        Ok(RecordsProgress {
+            file: self.file.clone(),
+            bytes: self.bytes,
+        })
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchCheckpoint {
     pub start_range: BigUint,
     pub end_range: BigUint,
     pub current_number: BigUint,
-    pub max_iterations: u32,
+    pub max_iterations: u64,
+    #[serde(default = "default_radix")]
+    pub radix: u32,
+    #[serde(default)]
+    pub classify: bool,
     pub numbers_tested: u64,
+    /// Candidates of checkpoints without a records file
+    #[serde(default)]
     pub potential_lychrel_found: Vec<BigUint>,
+    /// Counts and statistics tables so far; absent in older checkpoints
+    #[serde(default)]
+    pub summary: Option<SearchSummary>,
+    /// Where the candidates and palindromes found so far are kept, so a
+    /// resumed search ends with the same results as an uninterrupted one
+    #[serde(default)]
+    pub records: Option<RecordsProgress>,
     pub checkpoint_interval: Option<u64>,
     pub checkpoint_file: Option<String>,
     pub elapsed_secs: f64,
     pub timestamp: String,
+    /// Chunk progress of a parallel search, absent for sequential checkpoints
+    #[serde(default)]
+    pub parallel: Option<ChunkProgress>,
+    /// Thread cache size the search was started with
+    #[serde(default)]
+    pub cache_size: Option<usize>,
+    /// Numbers searched instead of the start..=end range
+    #[serde(default)]
+    pub source: Option<NumberSource>,
+    /// How far a search over `source` got
+    #[serde(default)]
+    pub source_progress: Option<SourceProgress>,
+}
+
+fn default_radix() -> u32 {
+    10
 }
 
 #[derive(Debug, Clone, Default)]
@@ -21,12 +193,20 @@ pub struct SearchCheckpointBuilder {
     pub start_range: Option<BigUint>,
     pub end_range: Option<BigUint>,
     pub current_number: Option<BigUint>,
-    pub max_iterations: Option<u32>,
+    pub max_iterations: Option<u64>,
+    pub radix: Option<u32>,
+    pub classify: Option<bool>,
     pub numbers_tested: Option<u64>,
     pub potential_lychrel: Option<Vec<IterationResult>>,
+    pub summary: Option<SearchSummary>,
+    pub records: Option<RecordsProgress>,
     pub checkpoint_interval: Option<u64>,
     pub checkpoint_file: Option<String>,
     pub elapsed_secs: Option<f64>,
+    pub parallel: Option<ChunkProgress>,
+    pub cache_size: Option<usize>,
+    pub source: Option<NumberSource>,
+    pub source_progress: Option<SourceProgress>,
 }
 
 impl SearchCheckpointBuilder {
@@ -49,11 +229,21 @@ impl SearchCheckpointBuilder {
         self
     }
 
-    pub fn max_iterations(mut self, value: u32) -> Self {
+    pub fn max_iterations(mut self, value: u64) -> Self {
         self.max_iterations = Some(value);
         self
     }
 
+    pub fn radix(mut self, value: u32) -> Self {
+        self.radix = Some(value);
+        self
+    }
+
+    pub fn classify(mut self, value: bool) -> Self {
+        self.classify = Some(value);
+        self
+    }
+
     pub fn numbers_tested(mut self, value: u64) -> Self {
         self.numbers_tested = Some(value);
         self
@@ -64,6 +254,16 @@ impl SearchCheckpointBuilder {
         self
     }
 
+    pub fn summary(mut self, value: SearchSummary) -> Self {
+        self.summary = Some(value);
+        self
+    }
+
+    pub fn records(mut self, value: RecordsProgress) -> Self {
+        self.records = Some(value);
+        self
+    }
+
     pub fn checkpoint_interval(mut self, value: Option<u64>) -> Self {
         self.checkpoint_interval = value;
         self
@@ -79,13 +279,33 @@ impl SearchCheckpointBuilder {
         self
     }
 
+    pub fn parallel(mut self, value: ChunkProgress) -> Self {
+        self.parallel = Some(value);
+        self
+    }
+
+    pub fn cache_size(mut self, value: Option<usize>) -> Self {
+        self.cache_size = value;
+        self
+    }
+
+    pub fn source(mut self, value: NumberSource) -> Self {
+        self.source = Some(value);
+        self
+    }
+
+    pub fn source_progress(mut self, value: SourceProgress) -> Self {
+        self.source_progress = Some(value);
+        self
+    }
+
     pub fn build(self) -> SearchCheckpoint {
         let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
         let potential_lychrel_found = self
             .potential_lychrel
-            .unwrap_or_default()
-            .iter()
-            .map(|r| r.start_number.clone())
+            .into_iter()
+            .flatten()
+            .map(|r| r.start_number)
             .collect();
 
         SearchCheckpoint {
@@ -93,12 +313,20 @@ impl SearchCheckpointBuilder {
             end_range: self.end_range.unwrap_or_default(),
             current_number: self.current_number.unwrap_or_default(),
             max_iterations: self.max_iterations.unwrap_or_default(),
+            radix: self.radix.unwrap_or_else(default_radix),
+            classify: self.classify.unwrap_or_default(),
             numbers_tested: self.numbers_tested.unwrap_or_default(),
             potential_lychrel_found,
+            summary: self.summary,
+            records: self.records,
             checkpoint_interval: self.checkpoint_interval,
             checkpoint_file: self.checkpoint_file,
             elapsed_secs: self.elapsed_secs.unwrap_or_default(),
             timestamp,
+            parallel: self.parallel,
+            cache_size: self.cache_size,
+            source: self.source,
+            source_progress: self.source_progress,
         }
     }
 }
@@ -112,31 +340,56 @@ impl SearchCheckpoint {
         crate::io_utils::load_from_file_str(filepath)
     }
 
+    /// Potential Lychrel numbers found before the checkpoint
+    pub fn potential_lychrel_count(&self) -> u64 {
+        match self.summary {
+            Some(ref summary) => summary.potential_lychrel,
+            None => self.potential_lychrel_found.len() as u64,
+        }
+    }
+
+    /// Whether the search ran in parallel mode
+    pub fn is_parallel(&self) -> bool {
+        match self.source_progress {
+            Some(ref progress) => progress.parallel,
+            None => self.parallel.is_some(),
+        }
+    }
+
+    /// Share of the numbers tested, 0 for sources of unknown size
     pub fn progress_percentage(&self) -> f64 {
-        if self.end_range <= self.start_range {
+        if self.source.is_none() && self.end_range <= self.start_range {
             return 100.0;
         }
-
-        let total = if let (Ok(start), Ok(end)) = (
-            self.start_range.to_string().parse::<u64>(),
-            self.end_range.to_string().parse::<u64>(),
-        ) {
-            end - start + 1
-        } else {
+        let Some(total) = self.total_numbers() else {
             return 0.0;
         };
+        if total == BigUint::default() {
+            return 100.0;
+        }
+
+        let total = total.to_f64().unwrap_or(f64::MAX);
+        (self.numbers_tested as f64 / total) * 100.0
+    }
 
-        (self.numbers_tested as f64 / total as f64) * 100.0
+    /// Numbers left to test, also correct for parallel checkpoints with gaps
+    ///
+    /// `None` for sources of unknown size, such as files and stdin.
+    pub fn numbers_remaining(&self) -> Option<u64> {
+        let total = self.total_numbers()?;
+        if total <= BigUint::from(self.numbers_tested) {
+            return Some(0);
+        }
+        Some((total - self.numbers_tested).to_u64().unwrap_or(u64::MAX))
     }
 
-    pub fn numbers_remaining(&self) -> u64 {
-        if let (Ok(current), Ok(end)) = (
-            self.current_number.to_string().parse::<u64>(),
-            self.end_range.to_string().parse::<u64>(),
-        ) {
-            end.saturating_sub(current)
+    fn total_numbers(&self) -> Option<BigUint> {
+        if let Some(ref source) = self.source {
+            source.count(self.radix)
+        } else if self.end_range < self.start_range {
+            Some(BigUint::default())
         } else {
-            0
+            Some(&self.end_range - &self.start_range + 1u32)
         }
     }
 }
@@ -0,0 +1,287 @@
+use crate::lychrel::{lychrel_iteration_radix, IterationResult};
+use crate::search::{search_range, SearchConfig};
+use crate::search_stats::SearchStatistics;
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Range and iteration schedule of a multi-pass search
+pub struct MultiPassConfig {
+    pub start: BigUint,
+    pub end: BigUint,
+    /// Iteration limit of each pass, e.g. `[100, 1000, 10000, 100000]`
+    pub schedule: Vec<u64>,
+    pub radix: u32,
+    pub parallel: bool,
+    /// Thread cache size of the first pass, which sees every number
+    pub cache_size: Option<usize>,
+}
+
+/// Outcome of one pass of a multi-pass search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchPass {
+    /// Total iteration limit of this pass
+    pub max_iterations: u64,
+    /// Numbers that entered this pass
+    pub tested: u64,
+    /// Numbers that reached a palindrome within this pass's limit
+    pub dropped: Vec<IterationResult>,
+    /// Numbers still without a palindrome at the end of the pass
+    pub survivors: u64,
+}
+
+/// Results of a multi-pass search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiPassResults {
+    pub total_tested: u64,
+    pub palindrome_starts: u64,
+    pub passes: Vec<SearchPass>,
+    /// Survivors of the last pass, tested to its full limit
+    pub potential_lychrel: Vec<IterationResult>,
+    /// Statistics tables over all passes, as a single search at the last limit would give
+    pub statistics: SearchStatistics,
+}
+
+/// Parse an iteration schedule such as `100,1000,1e4`
+///
+/// # Arguments
+///
+/// * `s` - Comma separated iteration limits, each larger than the one before
+///
+/// # Returns
+///
+/// The limits in order, or a message describing why the schedule is invalid
+///
+/// # Examples
+///
+/// ```
+/// use lychrel_finder::search_passes::parse_schedule;
+///
+/// assert_eq!(parse_schedule("100,1e3").unwrap(), vec![100, 1000]);
+/// assert!(parse_schedule("1000,100").is_err());
+/// ```
+pub fn parse_schedule(s: &str) -> Result<Vec<u64>, String> {
+    let mut schedule: Vec<u64> = Vec::new();
+    for part in s.split(',') {
+        let limit = crate::search::parse_bound(part)?;
+        let limit = limit
+            .to_u64()
+            .ok_or_else(|| format!("'{}' is too large", part.trim()))?;
+        if schedule.last().map_or(limit == 0, |&last| limit <= last) {
+            return Err("iteration limits must be positive and increasing".to_string());
+        }
+        schedule.push(limit);
+    }
+    Ok(schedule)
+}
+
+/// Search a range in passes of increasing iteration limits
+///
+/// The first pass tests every number up to the first limit. Each later pass
+/// only takes the survivors of the pass before it and continues their
+/// iteration from the value where it stopped, so no iteration is repeated.
+/// Numbers reaching a palindrome are recorded in the pass where they drop
+/// out, with their total iteration count. The schedule is taken in
+/// increasing order; zero and repeated limits are ignored.
+///
+/// # Arguments
+///
+/// * `config` - Range, iteration schedule, radix and parallel flag
+///
+/// # Returns
+///
+/// A `MultiPassResults` with one entry per pass and the final survivors
+///
+/// # Examples
+///
+/// ```
+/// use lychrel_finder::search_passes::{search_multi_pass, MultiPassConfig};
+/// use num_bigint::BigUint;
+///
+/// let results = search_multi_pass(MultiPassConfig {
+///     start: BigUint::from(1u32),
+///     end: BigUint::from(200u32),
+///     schedule: vec![10, 100, 1000],
+///     radix: 10,
+///     parallel: true,
+///     cache_size: None,
+/// });
+///
+/// // 89 and 98 need 24 iterations, so they drop out in the second pass
+/// assert!(results.passes[1]
+///     .dropped
+///     .iter()
+///     .any(|r| r.start_number == BigUint::from(89u32) && r.iterations == 24));
+/// assert_eq!(results.potential_lychrel.len(), 1);
+/// ```
+pub fn search_multi_pass(config: MultiPassConfig) -> MultiPassResults {
+    let mut schedule = config.schedule;
+    schedule.retain(|&limit| limit > 0);
+    schedule.sort_unstable();
+    schedule.dedup();
+
+    let mut results = MultiPassResults {
+        total_tested: 0,
+        palindrome_starts: 0,
+        passes: Vec::new(),
+        potential_lychrel: Vec::new(),
+        statistics: SearchStatistics::new(),
+    };
+    let Some(&first_limit) = schedule.first() else {
+        return results;
+    };
+    let radix = config.radix;
+
+    let first = search_range(SearchConfig {
+        start: config.start,
+        end: config.end,
+        max_iterations: first_limit,
+        radix,
+        classify: false,
+        parallel: config.parallel,
+        checkpoint_interval: None,
+        checkpoint_file: None,
+        cache_size: config.cache_size,
+    });
+    results.total_tested = first.total_tested;
+    results.palindrome_starts = first.palindrome_starts;
+
+    let mut survivors = first.potential_lychrel;
+    let dropped = first.palindromes_found;
+    results.passes.push(SearchPass {
+        max_iterations: first_limit,
+        tested: first.total_tested - first.palindrome_starts,
+        dropped,
+        survivors: survivors.len() as u64,
+    });
+
+    for limits in schedule.windows(2) {
+        let (previous, limit) = (limits[0], limits[1]);
+        let tested = survivors.len() as u64;
+        let continued: Vec<IterationResult> = if config.parallel {
+            survivors
+                .into_par_iter()
+                .map(|r| continue_iteration(r, previous, limit, radix))
+                .collect()
+        } else {
+            survivors
+                .into_iter()
+                .map(|r| continue_iteration(r, previous, limit, radix))
+                .collect()
+        };
+
+        let (dropped, remaining): (Vec<_>, Vec<_>) =
+            continued.into_iter().partition(|r| r.is_palindrome);
+        survivors = remaining;
+        results.passes.push(SearchPass {
+            max_iterations: limit,
+            tested,
+            dropped,
+            survivors: survivors.len() as u64,
+        });
+    }
+
+    for result in results.passes.iter().flat_map(|pass| &pass.dropped) {
+        results.statistics.record(result, radix);
+    }
+    for result in &survivors {
+        results.statistics.record(result, radix);
+    }
+    results.potential_lychrel = survivors;
+    results
+}
+
+/// Carry a survivor from `previous` iterations on to `limit`
+///
+/// The survivor's final number is the value after `previous` iterations;
+/// it is not a palindrome, or the number would not have survived. Survivors
+/// answered by the thread cache have no final number and start over.
+fn continue_iteration(
+    survivor: IterationResult,
+    previous: u64,
+    limit: u64,
+    radix: u32,
+) -> IterationResult {
+    let Some(state) = survivor.final_number else {
+        return lychrel_iteration_radix(survivor.start_number, limit, radix);
+    };
+    let continued = lychrel_iteration_radix(state, limit - previous, radix);
+
+    IterationResult {
+        start_number: survivor.start_number,
+        iterations: previous + continued.iterations,
+        ..continued
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passes_match_single_search() {
+        let single = search_range(SearchConfig {
+            start: BigUint::from(1u32),
+            end: BigUint::from(3000u32),
+            max_iterations: 300,
+            radix: 10,
+            classify: false,
+            parallel: false,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            cache_size: None,
+        });
+
+        // The cached first pass hands on the same final values
+        for (parallel, cache_size) in [(false, None), (true, None), (true, Some(10_000))] {
+            let results = search_multi_pass(MultiPassConfig {
+                start: BigUint::from(1u32),
+                end: BigUint::from(3000u32),
+                schedule: vec![20, 100, 300],
+                radix: 10,
+                parallel,
+                cache_size,
+            });
+
+            assert_eq!(results.total_tested, 3000);
+            assert_eq!(results.palindrome_starts, single.palindrome_starts);
+            assert_eq!(results.statistics, single.statistics);
+
+            let lychrel: Vec<_> = results
+                .potential_lychrel
+                .iter()
+                .map(|r| (r.start_number.clone(), r.iterations, r.final_number.clone()))
+                .collect();
+            let expected: Vec<_> = single
+                .potential_lychrel
+                .iter()
+                .map(|r| (r.start_number.clone(), r.iterations, r.final_number.clone()))
+                .collect();
+            assert_eq!(lychrel, expected);
+
+            // Every number drops out in the pass covering its iteration count
+            let mut lower = 0;
+            for pass in &results.passes {
+                assert!(pass
+                    .dropped
+                    .iter()
+                    .all(|r| r.iterations > lower && r.iterations <= pass.max_iterations));
+                assert_eq!(pass.tested, pass.dropped.len() as u64 + pass.survivors);
+                lower = pass.max_iterations;
+            }
+            assert_eq!(results.passes[1].tested, results.passes[0].survivors);
+        }
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        assert_eq!(
+            parse_schedule("100, 1000,1e4,100000").unwrap(),
+            vec![100, 1000, 10_000, 100_000]
+        );
+        for bad in ["", "0,10", "10,10", "100,abc", "1e30"] {
+            assert!(parse_schedule(bad).is_err(), "{} should be rejected", bad);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::lychrel::{IterationOutcome, IterationResult};
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Distribution tables of a search run
+///
+/// These are the tables usually published for a range: how many numbers
+/// need each iteration count, the most-delayed (smallest) number for each
+/// count, and how many potential Lychrel numbers each digit length holds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchStatistics {
+    /// Iterations to reach a palindrome -> number of start values
+    pub iteration_histogram: BTreeMap<u64, u64>,
+    /// Smallest start value reaching a palindrome after exactly that many iterations
+    pub most_delayed: BTreeMap<u64, BigUint>,
+    /// Digit length (in the search radix) -> number of potential Lychrel numbers
+    pub lychrel_by_digits: BTreeMap<usize, u64>,
+}
+
+impl SearchStatistics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a tested number to the tables
+    ///
+    /// Palindrome starts (zero iterations) are not part of any table.
+    pub fn record(&mut self, result: &IterationResult, radix: u32) {
+        if result.is_potential_lychrel {
+            let digits = result.start_number.to_radix_le(radix).len();
+            *self.lychrel_by_digits.entry(digits).or_insert(0) += 1;
+        } else if result.iterations > 0 {
+            *self
+                .iteration_histogram
+                .entry(result.iterations)
+                .or_insert(0) += 1;
+            self.most_delayed
+                .entry(result.iterations)
+                .and_modify(|n| {
+                    if result.start_number < *n {
+                        *n = result.start_number.clone();
+                    }
+                })
+                .or_insert_with(|| result.start_number.clone());
+        }
+    }
+
+    /// Add a tested number given as a machine word
+    ///
+    /// Same tables as `record`, building a BigUint only when the number
+    /// becomes the most-delayed one for its iteration count.
+    pub(crate) fn record_outcome(&mut self, start: u128, outcome: IterationOutcome, radix: u32) {
+        if !outcome.is_palindrome {
+            let digits = start
+                .checked_ilog(radix as u128)
+                .map_or(1, |l| l as usize + 1);
+            *self.lychrel_by_digits.entry(digits).or_insert(0) += 1;
+        } else if outcome.iterations > 0 {
+            *self
+                .iteration_histogram
+                .entry(outcome.iterations)
+                .or_insert(0) += 1;
+            self.most_delayed
+                .entry(outcome.iterations)
+                .and_modify(|n| {
+                    if n.to_u128().is_none_or(|n| start < n) {
+                        *n = BigUint::from(start);
+                    }
+                })
+                .or_insert_with(|| BigUint::from(start));
+        }
+    }
+
+    /// Combine the tables of two parts of the same search
+    pub fn merge(&mut self, other: SearchStatistics) {
+        for (iterations, count) in other.iteration_histogram {
+            *self.iteration_histogram.entry(iterations).or_insert(0) += count;
+        }
+        for (iterations, number) in other.most_delayed {
+            self.most_delayed
+                .entry(iterations)
+                .and_modify(|n| {
+                    if number < *n {
+                        *n = number.clone();
+                    }
+                })
+                .or_insert(number);
+        }
+        for (digits, count) in other.lychrel_by_digits {
+            *self.lychrel_by_digits.entry(digits).or_insert(0) += count;
+        }
+    }
+
+    /// Highest iteration count that still reached a palindrome
+    pub fn max_iterations_to_palindrome(&self) -> Option<u64> {
+        self.iteration_histogram.keys().next_back().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lychrel::{iterate, lychrel_iteration};
+    use crate::AdaptiveNumber;
+
+    #[test]
+    fn test_tables_below_200() {
+        let mut stats = SearchStatistics::new();
+        for n in 1u32..200 {
+            stats.record(&lychrel_iteration(BigUint::from(n), 500), 10);
+        }
+
+        // 89 and 98 are the only numbers below 200 needing 24 iterations
+        assert_eq!(stats.iteration_histogram[&24], 2);
+        assert_eq!(stats.most_delayed[&24], BigUint::from(89u32));
+        assert_eq!(stats.max_iterations_to_palindrome(), Some(24));
+        assert_eq!(stats.lychrel_by_digits.get(&3), Some(&1));
+        assert_eq!(stats.lychrel_by_digits.get(&2), None);
+    }
+
+    #[test]
+    fn test_outcomes_match_results() {
+        let mut from_results = SearchStatistics::new();
+        let mut from_outcomes = SearchStatistics::new();
+        for n in 1u32..1000 {
+            for radix in [2, 10] {
+                let mut current = AdaptiveNumber::from_u128_radix(n as u128, radix);
+                let outcome = iterate(&mut current, 100);
+                from_outcomes.record_outcome(n as u128, outcome, radix);
+                from_results.record(&outcome.into_result(BigUint::from(n), &current), radix);
+            }
+        }
+        assert_eq!(from_outcomes, from_results);
+    }
+
+    #[test]
+    fn test_merge_keeps_smallest() {
+        let mut low = SearchStatistics::new();
+        let mut high = SearchStatistics::new();
+        high.record(&lychrel_iteration(BigUint::from(98u32), 100), 10);
+        low.record(&lychrel_iteration(BigUint::from(89u32), 100), 10);
+
+        high.merge(low);
+        assert_eq!(high.iteration_histogram[&24], 2);
+        assert_eq!(high.most_delayed[&24], BigUint::from(89u32));
+    }
+}
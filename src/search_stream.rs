@@ -0,0 +1,448 @@
+use crate::lychrel::{iterate, lychrel_iteration_radix, IterationOutcome, IterationResult};
+use crate::search::{
+    cached_iteration, parallel_chunk_size, range_chunks, run_chunks, SearchConfig,
+};
+use crate::search_stats::SearchStatistics;
+use crate::thread_cache::ThreadCache;
+use crate::AdaptiveNumber;
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::sync::mpsc;
+
+/// Destination for the individual results of a streaming search
+///
+/// Every tested number is handed over exactly once, in ascending order,
+/// including numbers that are palindromes from the start.
+pub trait ResultSink {
+    fn record(&mut self, result: IterationResult);
+
+    /// Whether the sink looks at results at all
+    ///
+    /// A search can skip building results for a sink that does not.
+    fn wants_results(&self) -> bool {
+        true
+    }
+}
+
+/// Sink that discards every result, leaving only the aggregate summary
+pub struct NoResults;
+
+impl ResultSink for NoResults {
+    fn record(&mut self, _result: IterationResult) {}
+
+    fn wants_results(&self) -> bool {
+        false
+    }
+}
+
+/// Sink that hands each result to a closure
+pub struct CallbackSink<F: FnMut(IterationResult)>(pub F);
+
+impl<F: FnMut(IterationResult)> ResultSink for CallbackSink<F> {
+    fn record(&mut self, result: IterationResult) {
+        (self.0)(result)
+    }
+}
+
+impl ResultSink for mpsc::Sender<IterationResult> {
+    fn record(&mut self, result: IterationResult) {
+        // A receiver that hung up no longer wants results
+        let _ = self.send(result);
+    }
+}
+
+/// Sink that writes one JSON object per line
+///
+/// Writing stops at the first I/O error, which `finish` reports.
+pub struct NdjsonSink<W: Write> {
+    writer: W,
+    written: u64,
+    error: Option<std::io::Error>,
+}
+
+impl<W: Write> NdjsonSink<W> {
+    pub fn new(writer: W) -> Self {
+        NdjsonSink {
+            writer,
+            written: 0,
+            error: None,
+        }
+    }
+
+    /// Flush the writer
+    ///
+    /// # Returns
+    ///
+    /// The number of lines written, or the first error met while writing
+    pub fn finish(mut self) -> std::io::Result<u64> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        self.writer.flush()?;
+        Ok(self.written)
+    }
+}
+
+impl<W: Write> ResultSink for NdjsonSink<W> {
+    fn record(&mut self, result: IterationResult) {
+        if self.error.is_some() {
+            return;
+        }
+        let line = serde_json::to_writer(&mut self.writer, &result)
+            .map_err(std::io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"));
+        match line {
+            Ok(()) => self.written += 1,
+            Err(e) => self.error = Some(e),
+        }
+    }
+}
+
+/// Counts and statistics tables of a search, without individual records
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchSummary {
+    pub total_tested: u64,
+    pub potential_lychrel: u64,
+    pub palindromes_found: u64,
+    pub palindrome_starts: u64,
+    pub statistics: SearchStatistics,
+    /// Numbers answered from the thread cache
+    #[serde(default)]
+    pub cache_hits: u64,
+    /// Numbers tested in full while a thread cache was in use
+    #[serde(default)]
+    pub cache_misses: u64,
+}
+
+impl SearchSummary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Share of numbers answered from the thread cache, `None` if no cache was used
+    pub fn cache_hit_rate(&self) -> Option<f64> {
+        let total = self.cache_hits + self.cache_misses;
+        (total > 0).then(|| self.cache_hits as f64 / total as f64)
+    }
+
+    /// Count a single tested number given as a machine word
+    fn record_outcome(&mut self, start: u128, outcome: IterationOutcome, radix: u32) {
+        self.total_tested += 1;
+        self.statistics.record_outcome(start, outcome, radix);
+
+        if !outcome.is_palindrome {
+            self.potential_lychrel += 1;
+        } else if outcome.iterations > 0 {
+            self.palindromes_found += 1;
+        } else {
+            self.palindrome_starts += 1;
+        }
+    }
+
+    /// Count a tested number along with whether the thread cache answered it
+    fn record_tested(&mut self, result: &IterationResult, cache_hit: Option<bool>, radix: u32) {
+        self.record(result, radix);
+        match cache_hit {
+            Some(true) => self.cache_hits += 1,
+            Some(false) => self.cache_misses += 1,
+            None => {}
+        }
+    }
+
+    /// Add the counts of another part of the same search
+    fn merge(&mut self, other: SearchSummary) {
+        self.total_tested += other.total_tested;
+        self.potential_lychrel += other.potential_lychrel;
+        self.palindromes_found += other.palindromes_found;
+        self.palindrome_starts += other.palindrome_starts;
+        self.statistics.merge(other.statistics);
+        self.cache_hits += other.cache_hits;
+        self.cache_misses += other.cache_misses;
+    }
+
+    /// Count a single tested number
+    pub fn record(&mut self, result: &IterationResult, radix: u32) {
+        self.total_tested += 1;
+        self.statistics.record(result, radix);
+
+        if result.is_potential_lychrel {
+            self.potential_lychrel += 1;
+        } else if result.iterations > 0 {
+            self.palindromes_found += 1;
+        } else {
+            self.palindrome_starts += 1;
+        }
+    }
+}
+
+/// Search a range and stream every result to a sink
+///
+/// Unlike `search_range`, nothing but the summary is kept in memory, so the
+/// range size is only limited by time. Parallel chunks that finish out of
+/// order are held back until the chunks before them are done, so the sink
+/// always sees ascending start numbers. Classification needs every candidate
+/// at once and is not applied here, and no checkpoints are written.
+/// A sink that does not want results, like `NoResults`, gets none built:
+/// ranges that fit in a u128 are then counted without any BigUint.
+///
+/// # Arguments
+///
+/// * `config` - Range, iteration limit, radix and parallel flag
+/// * `sink` - Destination of the individual results
+///
+/// # Returns
+///
+/// Counts and statistics tables of the whole range
+///
+/// # Examples
+///
+/// ```
+/// use lychrel_finder::search_stream::{search_range_streaming, CallbackSink};
+/// use lychrel_finder::SearchConfig;
+/// use num_bigint::BigUint;
+///
+/// let config = SearchConfig {
+///     start: BigUint::from(190u32),
+///     end: BigUint::from(199u32),
+///     max_iterations: 500,
+///     radix: 10,
+///     classify: false,
+///     parallel: true,
+///     checkpoint_interval: None,
+///     checkpoint_file: None,
+///     cache_size: None,
+/// };
+///
+/// let mut lychrel = Vec::new();
+/// let summary = search_range_streaming(
+///     config,
+///     &mut CallbackSink(|r: lychrel_finder::IterationResult| {
+///         if r.is_potential_lychrel {
+///             lychrel.push(r.start_number);
+///         }
+///     }),
+/// );
+/// assert_eq!(summary.total_tested, 10);
+/// assert_eq!(lychrel, vec![BigUint::from(196u32)]);
+/// ```
+pub fn search_range_streaming(config: SearchConfig, sink: &mut dyn ResultSink) -> SearchSummary {
+    let mut summary = SearchSummary::new();
+    if config.start > config.end {
+        return summary;
+    }
+
+    let radix = config.radix;
+    let max_iterations = config.max_iterations;
+    let mut cache = config.cache_size.map(ThreadCache::new);
+
+    if !sink.wants_results() {
+        if !config.parallel {
+            return summarize_chunk(
+                &config.start,
+                &config.end,
+                max_iterations,
+                radix,
+                cache.as_mut(),
+            );
+        }
+        let chunk_size = parallel_chunk_size(&config.start, &config.end);
+        run_chunks(
+            range_chunks(&config.start, &config.end, chunk_size),
+            &mut cache,
+            |(chunk_start, chunk_end), cache| {
+                summarize_chunk(chunk_start, chunk_end, max_iterations, radix, cache)
+            },
+            |_, chunk| summary.merge(chunk),
+        );
+        return summary;
+    }
+
+    let mut deliver = |(result, cache_hit): (IterationResult, Option<bool>)| {
+        summary.record_tested(&result, cache_hit, radix);
+        sink.record(result);
+    };
+
+    if !config.parallel {
+        let mut current = config.start.clone();
+        while current <= config.end {
+            deliver(test_number(
+                current.clone(),
+                max_iterations,
+                radix,
+                cache.as_mut(),
+            ));
+            current += 1u32;
+        }
+        return summary;
+    }
+
+    let chunk_size = parallel_chunk_size(&config.start, &config.end);
+    let chunks = range_chunks(&config.start, &config.end, chunk_size);
+
+    // Chunks that finished ahead of the next one in range order, at most
+    // the few run_chunks lets workers run ahead
+    let mut pending: BTreeMap<BigUint, Vec<(IterationResult, Option<bool>)>> = BTreeMap::new();
+    let mut next_chunk = config.start.clone();
+
+    run_chunks(
+        chunks,
+        &mut cache,
+        |(chunk_start, chunk_end), mut cache| {
+            let mut results = Vec::new();
+            let mut current = chunk_start.clone();
+            while current <= *chunk_end {
+                results.push(test_number(
+                    current.clone(),
+                    max_iterations,
+                    radix,
+                    cache.as_deref_mut(),
+                ));
+                current += 1u32;
+            }
+            results
+        },
+        |(chunk_start, _), results| {
+            pending.insert(chunk_start, results);
+            while let Some(results) = pending.remove(&next_chunk) {
+                results.into_iter().for_each(&mut deliver);
+                next_chunk += chunk_size;
+            }
+        },
+    );
+
+    summary
+}
+
+/// Count every number of a chunk without keeping any result
+///
+/// Without a thread cache, a chunk that fits in a u128 is counted on a
+/// machine word and never builds a BigUint.
+fn summarize_chunk(
+    start: &BigUint,
+    end: &BigUint,
+    max_iterations: u64,
+    radix: u32,
+    mut cache: Option<&mut ThreadCache>,
+) -> SearchSummary {
+    let mut summary = SearchSummary::new();
+    if let (None, Some(start), Some(end)) = (&cache, start.to_u128(), end.to_u128()) {
+        for n in start..=end {
+            let mut current = AdaptiveNumber::from_u128_radix(n, radix);
+            let outcome = iterate(&mut current, max_iterations);
+            summary.record_outcome(n, outcome, radix);
+        }
+        return summary;
+    }
+
+    let mut current = start.clone();
+    while current <= *end {
+        let (result, cache_hit) =
+            test_number(current.clone(), max_iterations, radix, cache.as_deref_mut());
+        summary.record_tested(&result, cache_hit, radix);
+        current += 1u32;
+    }
+    summary
+}
+
+/// Test one number, along with whether the cache answered it when there is one
+fn test_number(
+    start: BigUint,
+    max_iterations: u64,
+    radix: u32,
+    cache: Option<&mut ThreadCache>,
+) -> (IterationResult, Option<bool>) {
+    match cache {
+        Some(cache) => {
+            let (result, hit) = cached_iteration(start, max_iterations, radix, cache);
+            (result, Some(hit))
+        }
+        None => (lychrel_iteration_radix(start, max_iterations, radix), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::search_range;
+
+    fn config(parallel: bool) -> SearchConfig {
+        SearchConfig {
+            start: BigUint::from(1u32),
+            end: BigUint::from(3000u32),
+            max_iterations: 300,
+            radix: 10,
+            classify: false,
+            parallel,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            cache_size: None,
+        }
+    }
+
+    #[test]
+    fn test_streaming_matches_search_range() {
+        let results = search_range(config(false));
+
+        for parallel in [false, true] {
+            let mut starts = Vec::new();
+            let summary = search_range_streaming(
+                config(parallel),
+                &mut CallbackSink(|r: IterationResult| starts.push(r.start_number)),
+            );
+
+            assert_eq!(summary.total_tested, results.total_tested);
+            assert_eq!(
+                summary.potential_lychrel,
+                results.potential_lychrel.len() as u64
+            );
+            assert_eq!(
+                summary.palindromes_found,
+                results.palindromes_found.len() as u64
+            );
+            assert_eq!(summary.palindrome_starts, results.palindrome_starts);
+            assert_eq!(
+                summary.statistics.iteration_histogram.values().sum::<u64>(),
+                summary.palindromes_found
+            );
+            // Every number exactly once, in order
+            assert!(starts
+                .iter()
+                .zip(1u32..)
+                .all(|(n, i)| *n == BigUint::from(i)));
+            assert_eq!(starts.len(), 3000);
+        }
+    }
+
+    #[test]
+    fn test_summary_without_results_matches() {
+        for parallel in [false, true] {
+            for cache_size in [None, Some(1000)] {
+                let config = || SearchConfig {
+                    cache_size,
+                    ..config(parallel)
+                };
+                let counted = search_range_streaming(config(), &mut NoResults);
+                let delivered = search_range_streaming(config(), &mut CallbackSink(|_| {}));
+                assert_eq!(counted, delivered);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ndjson_and_channel_sinks() {
+        let mut sink = NdjsonSink::new(Vec::new());
+        let summary = search_range_streaming(config(true), &mut sink);
+        assert_eq!(sink.finish().unwrap(), summary.total_tested);
+
+        let (mut sender, receiver) = mpsc::channel();
+        search_range_streaming(config(false), &mut sender);
+        drop(sender);
+        assert_eq!(receiver.iter().count(), 3000);
+
+        // 89 takes 24 iterations, and so does its reverse 98
+        assert_eq!(summary.statistics.iteration_histogram.get(&24), Some(&2));
+    }
+}
@@ -12,7 +12,8 @@ pub struct SeedGenerator {
     current: BigUint,
     max: BigUint,
     digits: usize,
-    p10_max: BigUint,
+    radix: u32,
+    p10_max: BigUint, // radix^(digits-1): the place value of the leading digit
     pub mode: GeneratorMode,
     skip_count: u64,
     generated_count: u64,
@@ -21,14 +22,20 @@ pub struct SeedGenerator {
 impl SeedGenerator {
     /// Create a new seed generator
     pub fn new(digits: usize, mode: GeneratorMode) -> Self {
-        let min = BigUint::from(10u32).pow(digits as u32 - 1);
+        Self::with_radix(digits, mode, 10)
+    }
+
+    /// Create a new seed generator for numbers with `digits` digits in base `radix`
+    pub fn with_radix(digits: usize, mode: GeneratorMode, radix: u32) -> Self {
+        let min = BigUint::from(radix).pow(digits as u32 - 1);
         let p10_max = min.clone();
-        let max = BigUint::from(10u32).pow(digits as u32);
+        let max = BigUint::from(radix).pow(digits as u32);
 
         SeedGenerator {
             current: min,
             max,
             digits,
+            radix,
             p10_max,
             mode,
             skip_count: 0,
@@ -38,13 +45,24 @@ impl SeedGenerator {
 
     /// Create generator with custom starting point (for resuming)
     pub fn from_checkpoint(digits: usize, mode: GeneratorMode, current: BigUint) -> Self {
-        let p10_max = BigUint::from(10u32).pow(digits as u32 - 1);
-        let max = BigUint::from(10u32).pow(digits as u32);
+        Self::from_checkpoint_with_radix(digits, mode, current, 10)
+    }
+
+    /// Create generator in base `radix` with custom starting point (for resuming)
+    pub fn from_checkpoint_with_radix(
+        digits: usize,
+        mode: GeneratorMode,
+        current: BigUint,
+        radix: u32,
+    ) -> Self {
+        let p10_max = BigUint::from(radix).pow(digits as u32 - 1);
+        let max = BigUint::from(radix).pow(digits as u32);
 
         SeedGenerator {
             current,
             max,
             digits,
+            radix,
             p10_max,
             mode,
             skip_count: 0,
@@ -81,23 +99,27 @@ impl SeedGenerator {
         self.p10_max.clone()
     }
 
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
     fn generate_smart_random(&mut self) -> BigUint {
         use rand::Rng;
         let mut rng = rand::thread_rng();
 
-        let min = BigUint::from(10u32).pow(self.digits as u32 - 1);
+        let min = BigUint::from(self.radix).pow(self.digits as u32 - 1);
 
-        let mut random_digits = String::new();
+        let mut random_digits = Vec::with_capacity(self.digits);
         for i in 0..self.digits {
             let digit = if i == 0 {
-                rng.gen_range(1..=9)
+                rng.gen_range(1..self.radix)
             } else {
-                rng.gen_range(0..=9)
+                rng.gen_range(0..self.radix)
             };
-            random_digits.push(std::char::from_digit(digit, 10).unwrap());
+            random_digits.push(digit as u8);
         }
 
-        random_digits.parse().unwrap_or(min)
+        BigUint::from_radix_be(&random_digits, self.radix).unwrap_or(min)
     }
 
     fn generate_from_pattern(&mut self) -> BigUint {
@@ -123,11 +145,19 @@ impl SeedGenerator {
 
 /// Free function to check if a number is a potential seed
 pub fn is_potential_seed(n: &BigUint, p10_max: Option<&BigUint>) -> bool {
+    is_potential_seed_radix(n, p10_max, 10)
+}
+
+/// Check if a number is a potential seed when written in base `radix`
+///
+/// `p10_max` is the place value of the leading digit (`radix^(digits-1)`)
+/// and enables a fast first/last digit comparison.
+pub fn is_potential_seed_radix(n: &BigUint, p10_max: Option<&BigUint>, radix: u32) -> bool {
     use num_traits::ToPrimitive;
 
     // Fast arithmetic path
     if let Some(p10) = p10_max {
-        let last = (n % 10u32).to_u32().unwrap();
+        let last = (n % radix).to_u32().unwrap();
         let first = (n / p10).to_u32().unwrap();
         if last < first {
             return false;
@@ -137,7 +167,7 @@ pub fn is_potential_seed(n: &BigUint, p10_max: Option<&BigUint>) -> bool {
         }
     }
 
-    let digits = n.to_radix_le(10);
+    let digits = n.to_radix_le(radix);
     let len = digits.len();
     for i in 0..len / 2 {
         let left = digits[len - 1 - i]; // Most significant
@@ -171,7 +201,7 @@ impl Iterator for SeedGenerator {
                 return None;
             }
 
-            if is_potential_seed(&candidate, Some(&self.p10_max)) {
+            if is_potential_seed_radix(&candidate, Some(&self.p10_max), self.radix) {
                 self.generated_count += 1;
                 return Some(candidate);
             } else {
@@ -191,7 +221,7 @@ pub struct GeneratorStats {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lychrel::reverse_number;
+    use crate::lychrel::{reverse_number, reverse_number_radix};
 
     #[test]
     fn test_is_potential_seed() {
@@ -210,6 +240,22 @@ mod tests {
         assert!(!is_potential_seed(&BigUint::from(10000u32), Some(&p10_4)));
     }
 
+    #[test]
+    fn test_generator_base_2() {
+        // 5-digit base 2 numbers: 10000 (16) to 11111 (31)
+        let gen = SeedGenerator::with_radix(5, GeneratorMode::Sequential, 2);
+        let numbers: Vec<BigUint> = gen.collect();
+        assert!(numbers.iter().all(|n| *n >= BigUint::from(16u32)));
+        assert!(numbers.iter().all(|n| *n < BigUint::from(32u32)));
+
+        // 10011 reverses to 11001 (kept), 10110 reverses to 01101 (skipped)
+        assert!(numbers.contains(&BigUint::from(19u32)));
+        assert!(!numbers.contains(&BigUint::from(22u32)));
+        for n in &numbers {
+            assert!(reverse_number_radix(n, 2) >= *n);
+        }
+    }
+
     #[test]
     fn test_generator_sequential() {
         let mut gen = SeedGenerator::new(3, GeneratorMode::Sequential);
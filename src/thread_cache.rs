@@ -1,23 +1,162 @@
+use crate::AdaptiveNumber;
 use num_bigint::BigUint;
+use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ops::Deref;
 use std::path::Path;
 use std::sync::Arc;
 
+/// Values of a thread that `add_thread` stores, counted from its first one
+pub const THREAD_PREFIX: usize = 50;
+
+/// Mersenne prime 2^61 - 1, the modulus of key residues
+const RESIDUE_MODULUS: u64 = (1 << 61) - 1;
+
+/// What is known about the thread a cached value lies on
+///
+/// Iteration counts are all taken from the seed: the seed itself is 0
+/// iterations from the seed, the value after its first reverse-add step
+/// is 1, and so on. A value `iterations_from_seed` steps down the thread
+/// reaches the palindrome after `palindrome_at_iteration - iterations_from_seed`
+/// more steps.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThreadInfo {
     pub seed_number: String, // Store as String for serialization
-    pub iterations_from_seed: u32,
-    pub max_iterations_tested: u32,
+    /// Reverse-add steps from the seed to this value
+    pub iterations_from_seed: u64,
+    pub max_iterations_tested: u64,
     pub final_digits: usize,
     pub reached_palindrome: bool,
-    pub palindrome_at_iteration: Option<u32>,
+    pub palindrome_at_iteration: Option<u64>,
+}
+
+/// Cached values, along with the residues of their keys
+///
+/// A residue is the value modulo 2^61 - 1, which is cheap to get from the
+/// digits of a number in any base. Values whose residue no key has are
+/// misses without building their BigUint.
+#[derive(Debug, Clone, Default)]
+pub struct ThreadMap {
+    values: HashMap<BigUint, ThreadInfo>,
+    /// Keys with each residue
+    residues: HashMap<u64, usize>,
+    /// Bit length of the longest key ever inserted
+    max_bits: u64,
+}
+
+impl ThreadMap {
+    fn insert(&mut self, key: BigUint, info: ThreadInfo) {
+        let residue = residue(&key);
+        self.max_bits = self.max_bits.max(key.bits());
+        if self.values.insert(key, info).is_none() {
+            *self.residues.entry(residue).or_default() += 1;
+        }
+    }
+
+    fn remove(&mut self, key: &BigUint) {
+        if self.values.remove(key).is_some() {
+            let residue = residue(key);
+            if let Some(count) = self.residues.get_mut(&residue) {
+                *count -= 1;
+                if *count == 0 {
+                    self.residues.remove(&residue);
+                }
+            }
+        }
+    }
+
+    fn has_residue(&self, residue: u64) -> bool {
+        self.residues.contains_key(&residue)
+    }
+}
+
+impl Deref for ThreadMap {
+    type Target = HashMap<BigUint, ThreadInfo>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl FromIterator<(BigUint, ThreadInfo)> for ThreadMap {
+    fn from_iter<I: IntoIterator<Item = (BigUint, ThreadInfo)>>(iter: I) -> Self {
+        let mut map = ThreadMap::default();
+        for (key, info) in iter {
+            map.insert(key, info);
+        }
+        map
+    }
+}
+
+fn residue(value: &BigUint) -> u64 {
+    (value % RESIDUE_MODULUS).to_u64().unwrap_or_default()
+}
+
+/// Residue of a number straight from its native or digit form
+///
+/// Digits are taken a chunk below 2^32 at a time, and reduced by folding
+/// the bits above 2^61 back in, so no step divides.
+fn number_residue(number: &AdaptiveNumber) -> u64 {
+    match number {
+        AdaptiveNumber::Native { value, .. } => reduce(*value),
+        AdaptiveNumber::Digits(digits) => {
+            let radix = digits.radix() as u64;
+            let (mut per_chunk, mut scale) = (0, 1u64);
+            while scale * radix < 1 << 32 {
+                per_chunk += 1;
+                scale *= radix;
+            }
+
+            // Little-endian digits, so the most significant chunk comes first
+            digits
+                .digits()
+                .rchunks(per_chunk)
+                .fold(0u64, |residue, chunk| {
+                    let scale = if chunk.len() == per_chunk {
+                        scale
+                    } else {
+                        radix.pow(chunk.len() as u32)
+                    };
+                    let chunk = chunk
+                        .iter()
+                        .rev()
+                        .fold(0u64, |value, &digit| value * radix + digit as u64);
+                    reduce(residue as u128 * scale as u128 + chunk as u128)
+                })
+        }
+    }
+}
+
+/// A lower bound on the bit length of a number, from its native or digit form
+///
+/// Values only grow along a thread, so once this passes the longest cached
+/// key no later value can be a hit either.
+fn min_bits(number: &AdaptiveNumber) -> u64 {
+    match number {
+        AdaptiveNumber::Native { value, .. } => (u128::BITS - value.leading_zeros()) as u64,
+        AdaptiveNumber::Digits(digits) => {
+            (digits.len().saturating_sub(1) as u64) * digits.radix().ilog2() as u64
+        }
+    }
+}
+
+/// `x` modulo 2^61 - 1, for any `x` below 2^127
+fn reduce(x: u128) -> u64 {
+    let modulus = RESIDUE_MODULUS as u128;
+    let x = (x & modulus) + (x >> 61);
+    let x = ((x & modulus) + (x >> 61)) as u64;
+    if x >= RESIDUE_MODULUS {
+        x - RESIDUE_MODULUS
+    } else {
+        x
+    }
 }
 
 #[derive(Debug)]
 pub struct ThreadCache {
-    known_values: Arc<HashMap<BigUint, ThreadInfo>>,
-    snapshot: Option<Arc<HashMap<BigUint, ThreadInfo>>>,
+    known_values: Arc<ThreadMap>,
+    snapshot: Option<Arc<ThreadMap>>,
     max_cache_size: usize,
     hits: u64,
     misses: u64,
@@ -37,14 +176,14 @@ pub enum DetectionResult {
     },
     KnownThread {
         thread_info: ThreadInfo,
-        converged_at_iteration: u32,
+        converged_at_iteration: u64,
     },
 }
 
 impl ThreadCache {
     pub fn new(max_size: usize) -> Self {
         ThreadCache {
-            known_values: Arc::new(HashMap::new()),
+            known_values: Arc::new(ThreadMap::default()),
             snapshot: None,
             max_cache_size: max_size,
             hits: 0,
@@ -71,6 +210,37 @@ impl ThreadCache {
         }
     }
 
+    /// Check a value given in native or digit form
+    ///
+    /// Counts hits and misses like `check`, but only builds the BigUint of
+    /// values some cached key shares a residue with. Values longer than every
+    /// key are misses without taking their residue.
+    pub fn check_number(&mut self, number: &AdaptiveNumber) -> Option<ThreadInfo> {
+        let bits = min_bits(number);
+        let max_bits = self.known_values.max_bits.max(
+            self.snapshot
+                .as_ref()
+                .map_or(0, |snapshot| snapshot.max_bits),
+        );
+        if bits > max_bits {
+            self.misses += 1;
+            return None;
+        }
+
+        let residue = number_residue(number);
+        let candidate = self.known_values.has_residue(residue)
+            || self
+                .snapshot
+                .as_ref()
+                .is_some_and(|snapshot| snapshot.has_residue(residue));
+        if candidate {
+            self.check(&number.to_biguint())
+        } else {
+            self.misses += 1;
+            None
+        }
+    }
+
     /// Add a new thread to the cache
     pub fn add_thread(&mut self, path: &[BigUint], info: ThreadInfo) {
         if path.is_empty() {
@@ -81,10 +251,9 @@ impl ThreadCache {
 
         // Only cache the first few elements to avoid memory explosion
         // and because later elements are more likely to be cached by other seeds
-        let limit = 50; // Cache 50 iterations
-        for (i, val) in path.iter().enumerate().take(limit) {
+        for (i, val) in path.iter().enumerate().take(THREAD_PREFIX) {
             let mut val_info = info.clone();
-            val_info.iterations_from_seed += i as u32;
+            val_info.iterations_from_seed += i as u64;
 
             // Update iterations from seed
             map.insert(val.clone(), val_info);
@@ -93,8 +262,24 @@ impl ThreadCache {
         self.evict_if_needed();
     }
 
+    /// Add every value of a thread to the cache
+    ///
+    /// Unlike `add_thread`, nothing is skipped, so a later number that meets
+    /// the thread anywhere along `path` is detected. `path[i]` is taken to be
+    /// `i` iterations after the first value of the path.
+    pub fn add_full_thread(&mut self, path: &[BigUint], info: ThreadInfo) {
+        let map = Arc::make_mut(&mut self.known_values);
+        for (i, val) in path.iter().enumerate() {
+            let mut val_info = info.clone();
+            val_info.iterations_from_seed += i as u64;
+            map.insert(val.clone(), val_info);
+        }
+
+        self.evict_if_needed();
+    }
+
     /// Determine if a thread should be cached based on its properties
-    pub fn should_cache(&self, iterations: u32) -> bool {
+    pub fn should_cache(&self, iterations: u64) -> bool {
         iterations >= 50 // Only cache if it took some effort
     }
 
@@ -131,6 +316,17 @@ impl ThreadCache {
         }
     }
 
+    /// Largest number of entries the cache keeps
+    pub fn max_size(&self) -> usize {
+        self.max_cache_size
+    }
+
+    /// Continue the hit and miss counts of a cache saved earlier
+    pub fn restore_counts(&mut self, hits: u64, misses: u64) {
+        self.hits = hits;
+        self.misses = misses;
+    }
+
     /// Calculate current hit rate
     pub fn hit_rate(&self) -> f64 {
         let total = self.hits + self.misses;
@@ -165,7 +361,7 @@ impl ThreadCache {
     pub fn load_from_file(path: &Path, max_size: usize) -> std::io::Result<Self> {
         let string_map: HashMap<String, ThreadInfo> = crate::io_utils::load_from_file(path)?;
 
-        let known_values: HashMap<BigUint, ThreadInfo> = string_map
+        let known_values: ThreadMap = string_map
             .into_iter()
             .map(|(k, v)| (k.parse::<BigUint>().unwrap_or_default(), v))
             .collect();
@@ -189,7 +385,7 @@ impl ThreadCache {
         let map = Arc::make_mut(&mut self.known_values);
         let other_map = Arc::unwrap_or_clone(other.known_values);
 
-        for (key, info) in other_map {
+        for (key, info) in other_map.values {
             // Only merge if not exists or if the other has more iterations tested
             if let Some(existing) = map.get(&key) {
                 if info.max_iterations_tested > existing.max_iterations_tested {
@@ -204,22 +400,22 @@ impl ThreadCache {
     }
 
     /// Take a snapshot of the current cache
-    pub fn take_snapshot(&mut self) -> Arc<HashMap<BigUint, ThreadInfo>> {
+    pub fn take_snapshot(&mut self) -> Arc<ThreadMap> {
         self.snapshot = Some(self.known_values.clone());
         self.known_values.clone()
     }
 
     /// Restore cache from a snapshot/merged values
-    pub fn restore_snapshot(&mut self, _snapshot: Arc<HashMap<BigUint, ThreadInfo>>) {
+    pub fn restore_snapshot(&mut self, _snapshot: Arc<ThreadMap>) {
         // With Arc, take_snapshot doesn't clear known_values, so restore_snapshot
         // just clears the local snapshot reference.
         self.snapshot = None;
     }
 
     /// Create a new worker cache with a reference to the snapshot
-    pub fn new_worker(snapshot: Arc<HashMap<BigUint, ThreadInfo>>, max_size: usize) -> Self {
+    pub fn new_worker(snapshot: Arc<ThreadMap>, max_size: usize) -> Self {
         ThreadCache {
-            known_values: Arc::new(HashMap::new()),
+            known_values: Arc::new(ThreadMap::default()),
             snapshot: Some(snapshot),
             max_cache_size: max_size,
             hits: 0,
@@ -230,7 +426,7 @@ impl ThreadCache {
     /// Create a new empty cache (helper for reduce)
     pub fn new_empty(max_size: usize) -> Self {
         ThreadCache {
-            known_values: Arc::new(HashMap::new()),
+            known_values: Arc::new(ThreadMap::default()),
             snapshot: None,
             max_cache_size: max_size,
             hits: 0,
@@ -252,6 +448,7 @@ impl ThreadCache {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::digit_number::DigitNumber;
 
     #[test]
     fn test_cache_new() {
@@ -308,7 +505,7 @@ mod tests {
 
         // Add more entries than capacity
         for i in 0..20 {
-            let path = vec![BigUint::from(i * 1000u32)];
+            let path = vec![BigUint::from(i * 1000u64)];
             let info = ThreadInfo {
                 seed_number: i.to_string(),
                 iterations_from_seed: 0,
@@ -350,4 +547,60 @@ mod tests {
         assert_eq!(cache.misses, 3);
         assert!((cache.hit_rate() - 0.4).abs() < 0.01);
     }
+
+    #[test]
+    fn test_check_number_matches_check() {
+        let mut cache = ThreadCache::new(1000);
+        let big: BigUint = "123456789012345678901234567890123456789012345"
+            .parse()
+            .unwrap();
+        let info = ThreadInfo {
+            seed_number: "196".to_string(),
+            iterations_from_seed: 0,
+            max_iterations_tested: 100,
+            final_digits: 50,
+            reached_palindrome: false,
+            palindrome_at_iteration: None,
+        };
+        cache.add_thread(&[BigUint::from(887u32), big.clone()], info);
+
+        // Native and digit forms, in any base, find the same keys
+        for radix in [2, 10, 16] {
+            for value in [BigUint::from(887u32), big.clone()] {
+                let number = AdaptiveNumber::Digits(DigitNumber::from_biguint_radix(&value, radix));
+                assert!(cache.check_number(&number).is_some());
+                assert!(cache
+                    .check_number(&AdaptiveNumber::from_biguint_radix(&value, radix))
+                    .is_some());
+            }
+            assert!(cache
+                .check_number(&AdaptiveNumber::from_biguint_radix(&(&big + 1u32), radix))
+                .is_none());
+        }
+        assert_eq!(cache.hits, 12);
+        assert_eq!(cache.misses, 3);
+
+        // Evicted keys take their residues with them
+        let map = Arc::make_mut(&mut cache.known_values);
+        map.remove(&big);
+        assert!(!map.has_residue(residue(&big)));
+        assert!(map.has_residue(residue(&BigUint::from(887u32))));
+    }
+
+    #[test]
+    fn test_thread_info_deep_iterations_roundtrip() {
+        // Entries written with 32-bit counters still load
+        let legacy = r#"{"seed_number":"196","iterations_from_seed":3,"max_iterations_tested":1000,"final_digits":400,"reached_palindrome":false,"palindrome_at_iteration":null}"#;
+        let info: ThreadInfo = serde_json::from_str(legacy).unwrap();
+        assert_eq!(info.max_iterations_tested, 1000);
+
+        // Counts from deep verification no longer overflow
+        let deep = ThreadInfo {
+            max_iterations_tested: 5_000_000_000,
+            ..info
+        };
+        let json = serde_json::to_string(&deep).unwrap();
+        let back: ThreadInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.max_iterations_tested, 5_000_000_000);
+    }
 }
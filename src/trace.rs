@@ -0,0 +1,105 @@
+use crate::digit_number::DigitNumber;
+
+/// A single reverse-add step handed to a trace sink
+pub struct TraceStep<'a> {
+    pub iteration: u64,
+    pub previous: &'a DigitNumber,
+    pub current: &'a DigitNumber,
+    pub is_palindrome: bool,
+}
+
+/// Destination for per-iteration tracing of a verification run
+///
+/// `wants` is asked before each step so that the engine only keeps a copy of
+/// the previous value for steps that will actually be recorded.
+pub trait TraceSink {
+    /// Whether the step with this iteration number should be recorded
+    fn wants(&self, iteration: u64) -> bool;
+
+    /// Record a step previously accepted by `wants`
+    fn record(&mut self, step: &TraceStep);
+}
+
+/// Sink that discards every step (verification is silent by default)
+pub struct NoTrace;
+
+impl TraceSink for NoTrace {
+    fn wants(&self, _iteration: u64) -> bool {
+        false
+    }
+
+    fn record(&mut self, _step: &TraceStep) {}
+}
+
+/// How much of the iteration sequence the stdout sink prints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TraceMode {
+    #[default]
+    None,
+    /// Print the full addition every N iterations
+    Every(u64),
+    /// Print only the digit count every N iterations
+    DigitSummary(u64),
+}
+
+/// Sink that prints traced steps to stdout
+pub struct StdoutTrace {
+    pub mode: TraceMode,
+}
+
+impl StdoutTrace {
+    pub fn new(mode: TraceMode) -> Self {
+        StdoutTrace { mode }
+    }
+}
+
+impl TraceSink for StdoutTrace {
+    fn wants(&self, iteration: u64) -> bool {
+        match self.mode {
+            TraceMode::None => false,
+            TraceMode::Every(n) | TraceMode::DigitSummary(n) => {
+                n > 0 && iteration.is_multiple_of(n)
+            }
+        }
+    }
+
+    fn record(&mut self, step: &TraceStep) {
+        let marker = if step.is_palindrome {
+            " (palindrome)"
+        } else {
+            ""
+        };
+
+        match self.mode {
+            TraceMode::None => {}
+            TraceMode::Every(_) => println!(
+                "Iteration {}: {} + {} = {}{}",
+                step.iteration,
+                step.previous,
+                step.previous.reversed(),
+                step.current,
+                marker
+            ),
+            TraceMode::DigitSummary(_) => println!(
+                "Iteration {}: {} digits{}",
+                step.iteration,
+                step.current.len(),
+                marker
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stdout_trace_wants() {
+        assert!(!StdoutTrace::new(TraceMode::None).wants(10));
+        assert!(!StdoutTrace::new(TraceMode::Every(0)).wants(10));
+        assert!(StdoutTrace::new(TraceMode::Every(1)).wants(7));
+        assert!(StdoutTrace::new(TraceMode::DigitSummary(5)).wants(10));
+        assert!(!StdoutTrace::new(TraceMode::DigitSummary(5)).wants(11));
+    }
+}
@@ -0,0 +1,233 @@
+use crate::digit_number::DigitNumber;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// One value of a reverse-add sequence
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrajectoryStep {
+    /// Number of reverse-add steps from the start number (0 is the start itself)
+    pub iteration: u64,
+    /// Value in the trajectory's radix, omitted when longer than `max_value_digits`
+    pub value: Option<String>,
+    /// Number of digits of the value
+    pub digits: usize,
+    /// FNV-1a hash of the digits, present when the value is omitted
+    pub hash: Option<String>,
+    /// Carries produced by the addition that led to this value
+    pub carries: usize,
+    pub is_palindrome: bool,
+}
+
+/// Settings for recording a trajectory
+#[derive(Debug, Clone)]
+pub struct TrajectoryConfig {
+    pub max_iterations: u64,
+    pub radix: u32,
+    /// Values with more digits are stored as digit count and hash only
+    pub max_value_digits: usize,
+}
+
+impl Default for TrajectoryConfig {
+    fn default() -> Self {
+        TrajectoryConfig {
+            max_iterations: 1000,
+            radix: 10,
+            max_value_digits: 1000,
+        }
+    }
+}
+
+/// Iterator over every value of the reverse-add sequence of a number
+///
+/// The first step is the start number itself. The sequence ends after the
+/// first palindrome or after `max_iterations` reverse-add steps, so a number
+/// that reaches a palindrome yields `iterations + 1` steps.
+///
+/// # Examples
+///
+/// ```
+/// use lychrel_finder::trajectory::{Trajectory, TrajectoryConfig};
+/// use num_bigint::BigUint;
+///
+/// let steps: Vec<_> = Trajectory::new(&BigUint::from(89u32), TrajectoryConfig::default()).collect();
+/// assert_eq!(steps.len(), 25);
+/// assert!(steps.last().unwrap().is_palindrome);
+/// ```
+pub struct Trajectory {
+    current: DigitNumber,
+    config: TrajectoryConfig,
+    iteration: u64,
+    carries: usize,
+    finished: bool,
+}
+
+impl Trajectory {
+    pub fn new(start: &BigUint, config: TrajectoryConfig) -> Self {
+        Trajectory {
+            current: DigitNumber::from_biguint_radix(start, config.radix),
+            config,
+            iteration: 0,
+            carries: 0,
+            finished: false,
+        }
+    }
+}
+
+impl Iterator for Trajectory {
+    type Item = TrajectoryStep;
+
+    fn next(&mut self) -> Option<TrajectoryStep> {
+        if self.finished {
+            return None;
+        }
+
+        let is_palindrome = self.current.is_palindrome();
+        let digits = self.current.len();
+        let (value, hash) = if digits <= self.config.max_value_digits {
+            (Some(self.current.to_string()), None)
+        } else {
+            (None, Some(format!("{:016x}", fnv1a(self.current.digits()))))
+        };
+
+        let step = TrajectoryStep {
+            iteration: self.iteration,
+            value,
+            digits,
+            hash,
+            carries: self.carries,
+            is_palindrome,
+        };
+
+        if is_palindrome || self.iteration >= self.config.max_iterations {
+            self.finished = true;
+        } else {
+            self.carries = self.current.reverse_add_carries();
+            self.current.reverse_add();
+            self.iteration += 1;
+        }
+
+        Some(step)
+    }
+}
+
+/// FNV-1a over the little-endian digits, stable across runs and platforms
+fn fnv1a(digits: &[u8]) -> u64 {
+    digits.iter().fold(0xcbf29ce484222325, |hash, &d| {
+        (hash ^ d as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Output format for exported trajectories
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrajectoryFormat {
+    /// One JSON object per line
+    JsonLines,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+/// Stream the trajectory of a number to a writer
+///
+/// # Arguments
+///
+/// * `start` - The number to follow
+/// * `config` - Iteration limit, radix and value size limit
+/// * `format` - JSON lines or CSV
+/// * `writer` - Destination of the exported steps
+///
+/// # Returns
+///
+/// The last recorded step, or an I/O error from the writer
+pub fn write_trajectory<W: Write>(
+    start: &BigUint,
+    config: TrajectoryConfig,
+    format: TrajectoryFormat,
+    writer: &mut W,
+) -> std::io::Result<Option<TrajectoryStep>> {
+    if format == TrajectoryFormat::Csv {
+        writeln!(writer, "iteration,digits,carries,is_palindrome,value,hash")?;
+    }
+
+    let mut last = None;
+    for step in Trajectory::new(start, config) {
+        match format {
+            TrajectoryFormat::JsonLines => {
+                serde_json::to_writer(&mut *writer, &step)?;
+                writeln!(writer)?;
+            }
+            TrajectoryFormat::Csv => writeln!(
+                writer,
+                "{},{},{},{},{},{}",
+                step.iteration,
+                step.digits,
+                step.carries,
+                step.is_palindrome,
+                step.value.as_deref().unwrap_or(""),
+                step.hash.as_deref().unwrap_or("")
+            )?,
+        }
+        last = Some(step);
+    }
+
+    writer.flush()?;
+    Ok(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lychrel::lychrel_iteration;
+
+    #[test]
+    fn test_trajectory_matches_iteration() {
+        let start = BigUint::from(10911u32);
+        let steps: Vec<_> = Trajectory::new(&start, TrajectoryConfig::default()).collect();
+        let result = lychrel_iteration(start, 1000);
+
+        assert_eq!(steps.len() as u64, result.iterations + 1);
+        assert_eq!(steps[0].value.as_deref(), Some("10911"));
+        assert_eq!(steps[0].carries, 0);
+        assert_eq!(
+            steps.last().unwrap().value,
+            result.final_number.map(|n| n.to_string())
+        );
+        assert!(steps[..steps.len() - 1].iter().all(|s| !s.is_palindrome));
+    }
+
+    #[test]
+    fn test_huge_values_are_hashed() {
+        let config = TrajectoryConfig {
+            max_iterations: 30,
+            radix: 10,
+            max_value_digits: 10,
+        };
+        let steps: Vec<_> = Trajectory::new(&BigUint::from(196u32), config).collect();
+
+        assert_eq!(steps.len(), 31);
+        for step in &steps {
+            assert_eq!(step.value.is_some(), step.digits <= 10);
+            assert_eq!(step.hash.is_some(), step.digits > 10);
+        }
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let mut out = Vec::new();
+        let config = TrajectoryConfig::default();
+        write_trajectory(
+            &BigUint::from(89u32),
+            config,
+            TrajectoryFormat::Csv,
+            &mut out,
+        )
+        .unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 26);
+        assert_eq!(lines[1], "0,2,0,false,89,");
+        assert_eq!(lines[2], "1,3,2,false,187,");
+        assert_eq!(lines[25], "24,13,0,true,8813200023188,");
+    }
+}
@@ -1,13 +1,33 @@
 use crate::checkpoint::Checkpoint;
+use crate::digit_number::DigitNumber;
+use crate::limb_number::LimbNumber;
+use crate::pair_sum::{PairSumNumber, PairSumState};
+use crate::trace::{NoTrace, TraceSink, TraceStep};
 use num_bigint::BigUint;
 use std::time::Instant;
 
+/// Digit count above which verification switches to the limb engine
+pub const DEFAULT_LIMB_THRESHOLD: usize = 10_000;
+
+/// Number representation used for the reverse-add steps of a verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerifyEngine {
+    /// Digit vector, moving to base-10^16 limbs above the limb threshold
+    #[default]
+    Standard,
+    /// Pair sums of the previous value, one byte per two digits
+    PairSum,
+}
+
 pub struct VerifyConfig {
     pub number: BigUint,
     pub max_iterations: u64,
     pub progress_interval: u64,
     pub checkpoint_interval: Option<u64>,
     pub checkpoint_file: Option<String>,
+    /// Digit count above which the base-10^16 limb engine is used
+    pub limb_threshold: usize,
+    pub engine: VerifyEngine,
 }
 
 pub struct VerifyResult {
@@ -15,20 +35,137 @@ pub struct VerifyResult {
     pub is_palindrome: bool,
     pub iterations_completed: u64,
     pub final_number: Option<BigUint>,
+    /// False if a palindrome was found or the run stopped on a stop request
     pub is_potential_lychrel: bool,
     pub total_time: std::time::Duration,
 }
 
-fn reverse_number(n: &BigUint) -> BigUint {
-    let s = n.to_string();
-    let reversed = s.chars().rev().collect::<String>();
-    reversed.parse().unwrap()
+/// Working value of a verification run
+///
+/// Starts as a digit vector and moves to limbs once it has more than
+/// `limb_threshold` digits, or to pair sums on the first step with the
+/// pair-sum engine. The move is one-way.
+enum VerifyNumber {
+    Digits(DigitNumber),
+    Limbs(LimbNumber),
+    Pairs(PairSumNumber),
 }
 
-fn is_palindrome(n: &BigUint) -> bool {
-    let s = n.to_string();
-    let reversed: String = s.chars().rev().collect();
-    s == reversed
+impl VerifyNumber {
+    fn new(n: &BigUint, limb_threshold: usize, engine: VerifyEngine) -> Self {
+        let digits = DigitNumber::from_biguint(n);
+        // The pair-sum engine takes its first step from digits at any length
+        if engine == VerifyEngine::Standard && digits.len() > limb_threshold {
+            VerifyNumber::Limbs(LimbNumber::from_digit_number(&digits))
+        } else {
+            VerifyNumber::Digits(digits)
+        }
+    }
+
+    /// Restore the working value of a checkpoint, preferring its pair-sum form
+    ///
+    /// `None` if the checkpoint holds neither valid pair sums nor a current number.
+    fn from_checkpoint(checkpoint: &Checkpoint, engine: VerifyEngine) -> Option<Self> {
+        if let Some(ref state) = checkpoint.pair_sums {
+            match PairSumNumber::from_state(state) {
+                Some(pairs) => return Some(VerifyNumber::Pairs(pairs)),
+                None => eprintln!("Warning: Invalid pair sums in checkpoint"),
+            }
+        }
+        checkpoint
+            .current_number
+            .as_ref()
+            .map(|n| VerifyNumber::new(n, checkpoint.limb_threshold, engine))
+    }
+
+    fn is_palindrome(&self) -> bool {
+        match self {
+            VerifyNumber::Digits(n) => n.is_palindrome(),
+            VerifyNumber::Limbs(n) => n.is_palindrome(),
+            VerifyNumber::Pairs(n) => n.is_palindrome(),
+        }
+    }
+
+    fn reverse_add(&mut self, engine: VerifyEngine, limb_threshold: usize) -> bool {
+        match self {
+            VerifyNumber::Digits(n) if engine == VerifyEngine::PairSum => {
+                let pairs = PairSumNumber::from_digit_number(n);
+                let palindrome = pairs.is_palindrome();
+                *self = VerifyNumber::Pairs(pairs);
+                palindrome
+            }
+            VerifyNumber::Digits(n) => {
+                let palindrome = n.reverse_add();
+                if n.len() > limb_threshold {
+                    *self = VerifyNumber::Limbs(LimbNumber::from_digit_number(n));
+                }
+                palindrome
+            }
+            VerifyNumber::Limbs(n) => n.reverse_add(),
+            VerifyNumber::Pairs(n) => n.reverse_add(),
+        }
+    }
+
+    /// Current number and pair sums for a checkpoint
+    ///
+    /// A pair-sum run only stores its pair sums, so the number is left at 0
+    /// for `Checkpoint::with_pair_sums` to drop.
+    fn checkpoint_parts(&self) -> (BigUint, Option<PairSumState>) {
+        match self {
+            VerifyNumber::Pairs(n) => (BigUint::default(), Some(n.to_state())),
+            _ => (self.to_biguint(), None),
+        }
+    }
+
+    /// Decimal digit count, without converting the value
+    fn len(&self) -> usize {
+        match self {
+            VerifyNumber::Digits(n) => n.len(),
+            VerifyNumber::Limbs(n) => n.len(),
+            VerifyNumber::Pairs(n) => n.len(),
+        }
+    }
+
+    /// Digit vector copy for trace sinks
+    fn to_digit_number(&self) -> DigitNumber {
+        match self {
+            VerifyNumber::Digits(n) => n.clone(),
+            VerifyNumber::Limbs(n) => n.to_digit_number(),
+            VerifyNumber::Pairs(n) => n.to_digit_number(),
+        }
+    }
+
+    fn to_biguint(&self) -> BigUint {
+        match self {
+            VerifyNumber::Digits(n) => n.to_biguint(),
+            VerifyNumber::Limbs(n) => n.to_biguint(),
+            VerifyNumber::Pairs(n) => n.to_biguint(),
+        }
+    }
+}
+
+/// Apply one reverse-add step and hand it to the trace sink if it wants it
+fn traced_step(
+    current: &mut VerifyNumber,
+    iteration: u64,
+    engine: VerifyEngine,
+    limb_threshold: usize,
+    trace: &mut dyn TraceSink,
+) -> bool {
+    // Only keep a copy of the previous value when the sink will record it
+    let previous = trace.wants(iteration).then(|| current.to_digit_number());
+    let palindrome = current.reverse_add(engine, limb_threshold);
+
+    if let Some(ref previous) = previous {
+        trace.record(&TraceStep {
+            iteration,
+            previous,
+            current: &current.to_digit_number(),
+            is_palindrome: palindrome,
+        });
+    }
+
+    palindrome
 }
 
 /// Verify if a number is a Lychrel number with resumable progress and checkpointing
@@ -45,9 +182,11 @@ fn is_palindrome(n: &BigUint) -> bool {
 ///   - `progress_interval`: How often to show progress
 ///   - `checkpoint_interval`: How often to save checkpoints
 ///   - `checkpoint_file`: Optional checkpoint file path
+///   - `limb_threshold`: Digit count above which the limb engine takes over
+///   - `engine`: Standard digit/limb engine or pair-sum engine
 /// * `progress_callback` - Callback function that receives:
 ///   - Current iteration count
-///   - Current number value
+///   - Digit count of the current number
 ///   - Time elapsed
 ///   - Whether this is a checkpoint save
 ///
@@ -58,7 +197,7 @@ fn is_palindrome(n: &BigUint) -> bool {
 /// # Examples
 ///
 /// ```
-/// use lychrel_finder::verify::{verify_lychrel_resumable, VerifyConfig};
+/// use lychrel_finder::verify::{verify_lychrel_resumable, VerifyConfig, VerifyEngine};
 /// use num_bigint::BigUint;
 ///
 /// let config = VerifyConfig {
@@ -67,29 +206,69 @@ fn is_palindrome(n: &BigUint) -> bool {
 ///     progress_interval: 10000,
 ///     checkpoint_interval: Some(10000),
 ///     checkpoint_file: Some("checkpoint_196.json".to_string()),
+///     limb_threshold: 10_000,
+///     engine: VerifyEngine::Standard,
 /// };
 ///
-/// let result = verify_lychrel_resumable(config, |iter, current, elapsed, is_checkpoint| {
+/// let result = verify_lychrel_resumable(config, |iter, digits, elapsed, is_checkpoint| {
 ///     if is_checkpoint {
 ///         println!("Checkpoint: {} iterations", iter);
 ///     }
 /// });
 /// ```
-pub fn verify_lychrel_resumable<F>(config: VerifyConfig, mut progress_callback: F) -> VerifyResult
+pub fn verify_lychrel_resumable<F>(config: VerifyConfig, progress_callback: F) -> VerifyResult
 where
-    F: FnMut(u64, &BigUint, std::time::Duration, bool),
+    F: FnMut(u64, usize, std::time::Duration, bool),
+{
+    verify_lychrel_traced(config, &mut NoTrace, progress_callback)
+}
+
+/// Verify a number while reporting individual reverse-add steps to a trace sink
+///
+/// Same as `verify_lychrel_resumable`, which is this function with a `NoTrace`
+/// sink. The sink decides which steps it wants, so tracing every Nth step or
+/// only digit counts costs nothing on the untraced iterations.
+///
+/// # Examples
+///
+/// ```
+/// use lychrel_finder::trace::{StdoutTrace, TraceMode};
+/// use lychrel_finder::verify::{verify_lychrel_traced, VerifyConfig, VerifyEngine};
+/// use num_bigint::BigUint;
+///
+/// let config = VerifyConfig {
+///     number: BigUint::from(89u32),
+///     max_iterations: 100,
+///     progress_interval: 10,
+///     checkpoint_interval: None,
+///     checkpoint_file: None,
+///     limb_threshold: 10_000,
+///     engine: VerifyEngine::Standard,
+/// };
+///
+/// let mut trace = StdoutTrace::new(TraceMode::DigitSummary(5));
+/// let result = verify_lychrel_traced(config, &mut trace, |_, _, _, _| {});
+/// assert_eq!(result.iterations_completed, 24);
+/// ```
+pub fn verify_lychrel_traced<F>(
+    config: VerifyConfig,
+    trace: &mut dyn TraceSink,
+    mut progress_callback: F,
+) -> VerifyResult
+where
+    F: FnMut(u64, usize, std::time::Duration, bool),
 {
     let start_time = Instant::now();
-    let mut current = config.number.clone();
+    let mut current = VerifyNumber::new(&config.number, config.limb_threshold, config.engine);
     let mut iteration_count: u64 = 0;
     let total_elapsed = 0.0f64;
 
-    if is_palindrome(&current) {
+    if current.is_palindrome() {
         return VerifyResult {
+            final_number: Some(config.number.clone()),
             start_number: config.number,
             is_palindrome: true,
             iterations_completed: 0,
-            final_number: Some(current),
             is_potential_lychrel: false,
             total_time: start_time.elapsed(),
         };
@@ -99,24 +278,18 @@ where
     let mut last_checkpoint = 0u64;
 
     while iteration_count < config.max_iterations {
-        let reversed = reverse_number(&current);
-        let previous = current.clone();
-        let reversed_clone = reversed.clone();
-        current += reversed;
         iteration_count += 1;
-
-        println!(
-            "Iteration {}: {} + {} = {}",
-            iteration_count, previous, reversed_clone, current
+        let palindrome = traced_step(
+            &mut current,
+            iteration_count,
+            config.engine,
+            config.limb_threshold,
+            trace,
         );
 
-        if is_palindrome(&current) {
-            progress_callback(iteration_count, &current, start_time.elapsed(), false);
-            println!("\n=== PALINDROME REACHED ===");
-            println!(
-                "Iteration {}: {} + {} = {}",
-                iteration_count, previous, reversed_clone, current
-            );
+        if palindrome {
+            progress_callback(iteration_count, current.len(), start_time.elapsed(), false);
+            let current = current.to_biguint();
             return VerifyResult {
                 start_number: config.number.clone(),
                 is_palindrome: true,
@@ -127,31 +300,37 @@ where
             };
         }
 
-        let should_save_checkpoint = if let Some(checkpoint_interval) = config.checkpoint_interval {
-            iteration_count - last_checkpoint >= checkpoint_interval
-        } else {
-            false
-        };
+        // A stop request saves a last checkpoint and ends the run
+        let stop = config.checkpoint_file.is_some() && crate::interrupt::stop_requested();
+        let should_save_checkpoint = stop
+            || if let Some(checkpoint_interval) = config.checkpoint_interval {
+                iteration_count - last_checkpoint >= checkpoint_interval
+            } else {
+                false
+            };
 
         let should_show_progress =
             iteration_count - last_progress_report >= config.progress_interval;
 
         if should_save_checkpoint {
             if let Some(ref checkpoint_file) = config.checkpoint_file {
+                let (number, pair_sums) = current.checkpoint_parts();
                 let checkpoint = Checkpoint::new(
                     config.number.clone(),
-                    current.clone(),
+                    number,
                     iteration_count,
                     config.max_iterations,
                     config.progress_interval,
                     config.checkpoint_interval,
                     total_elapsed + start_time.elapsed().as_secs_f64(),
-                );
+                )
+                .with_limb_threshold(config.limb_threshold)
+                .with_pair_sums(pair_sums);
 
                 if let Err(e) = checkpoint.save(checkpoint_file) {
                     eprintln!("Warning: Failed to save checkpoint: {}", e);
                 } else {
-                    progress_callback(iteration_count, &current, start_time.elapsed(), true);
+                    progress_callback(iteration_count, current.len(), start_time.elapsed(), true);
                     last_checkpoint = iteration_count;
                     if should_show_progress {
                         last_progress_report = iteration_count;
@@ -159,12 +338,24 @@ where
                 }
             }
         } else if should_show_progress {
-            progress_callback(iteration_count, &current, start_time.elapsed(), false);
+            progress_callback(iteration_count, current.len(), start_time.elapsed(), false);
             last_progress_report = iteration_count;
         }
+
+        if stop {
+            return VerifyResult {
+                start_number: config.number,
+                is_palindrome: false,
+                iterations_completed: iteration_count,
+                final_number: Some(current.to_biguint()),
+                is_potential_lychrel: false,
+                total_time: start_time.elapsed(),
+            };
+        }
     }
 
-    progress_callback(iteration_count, &current, start_time.elapsed(), false);
+    progress_callback(iteration_count, current.len(), start_time.elapsed(), false);
+    let current = current.to_biguint();
 
     VerifyResult {
         start_number: config.number,
@@ -213,38 +404,71 @@ pub fn resume_from_checkpoint<F>(
     checkpoint: Checkpoint,
     checkpoint_file: Option<String>,
     checkpoint_interval: Option<u64>,
+    progress_callback: F,
+) -> VerifyResult
+where
+    F: FnMut(u64, usize, std::time::Duration, bool),
+{
+    resume_from_checkpoint_traced(
+        checkpoint,
+        checkpoint_file,
+        checkpoint_interval,
+        &mut NoTrace,
+        progress_callback,
+    )
+}
+
+/// Resume verification from a checkpoint while reporting steps to a trace sink
+///
+/// Same as `resume_from_checkpoint`, which is this function with a `NoTrace` sink.
+pub fn resume_from_checkpoint_traced<F>(
+    checkpoint: Checkpoint,
+    checkpoint_file: Option<String>,
+    checkpoint_interval: Option<u64>,
+    trace: &mut dyn TraceSink,
     mut progress_callback: F,
 ) -> VerifyResult
 where
-    F: FnMut(u64, &BigUint, std::time::Duration, bool),
+    F: FnMut(u64, usize, std::time::Duration, bool),
 {
     let start_time = Instant::now();
-    let mut current = checkpoint.current_number.clone();
-    let mut iteration_count = checkpoint.iterations_completed;
+    // A checkpoint written by the pair-sum engine keeps using it
+    let engine = if checkpoint.pair_sums.is_some() {
+        VerifyEngine::PairSum
+    } else {
+        VerifyEngine::Standard
+    };
+    let (mut current, mut iteration_count) =
+        match VerifyNumber::from_checkpoint(&checkpoint, engine) {
+            Some(current) => (current, checkpoint.iterations_completed),
+            None => {
+                eprintln!(
+                    "Warning: Checkpoint has no usable current number, starting over from {}",
+                    checkpoint.start_number
+                );
+                let start =
+                    VerifyNumber::new(&checkpoint.start_number, checkpoint.limb_threshold, engine);
+                (start, 0)
+            }
+        };
     let base_elapsed = checkpoint.elapsed_secs;
 
     let mut last_progress_report = iteration_count;
     let mut last_checkpoint = iteration_count;
 
     while iteration_count < checkpoint.max_iterations {
-        let reversed = reverse_number(&current);
-        let previous = current.clone();
-        let reversed_clone = reversed.clone();
-        current += reversed;
         iteration_count += 1;
-
-        println!(
-            "Iteration {}: {} + {} = {}",
-            iteration_count, previous, reversed_clone, current
+        let palindrome = traced_step(
+            &mut current,
+            iteration_count,
+            engine,
+            checkpoint.limb_threshold,
+            trace,
         );
 
-        if is_palindrome(&current) {
-            progress_callback(iteration_count, &current, start_time.elapsed(), false);
-            println!("\n=== PALINDROME REACHED ===");
-            println!(
-                "Iteration {}: {} + {} = {}",
-                iteration_count, previous, reversed_clone, current
-            );
+        if palindrome {
+            progress_callback(iteration_count, current.len(), start_time.elapsed(), false);
+            let current = current.to_biguint();
             let total_duration = std::time::Duration::from_secs_f64(
                 base_elapsed + start_time.elapsed().as_secs_f64(),
             );
@@ -258,32 +482,37 @@ where
             };
         }
 
-        // Save checkpoint periodically
-        let should_save_checkpoint = if let Some(interval) = checkpoint_interval {
-            iteration_count - last_checkpoint >= interval
-        } else {
-            false
-        };
+        // Save checkpoint periodically, and a last one on a stop request
+        let stop = checkpoint_file.is_some() && crate::interrupt::stop_requested();
+        let should_save_checkpoint = stop
+            || if let Some(interval) = checkpoint_interval {
+                iteration_count - last_checkpoint >= interval
+            } else {
+                false
+            };
 
         let should_show_progress =
             iteration_count - last_progress_report >= checkpoint.progress_interval;
 
         if should_save_checkpoint {
             if let Some(ref file) = checkpoint_file {
+                let (number, pair_sums) = current.checkpoint_parts();
                 let new_checkpoint = Checkpoint::new(
                     checkpoint.start_number.clone(),
-                    current.clone(),
+                    number,
                     iteration_count,
                     checkpoint.max_iterations,
                     checkpoint.progress_interval,
                     checkpoint_interval,
                     base_elapsed + start_time.elapsed().as_secs_f64(),
-                );
+                )
+                .with_limb_threshold(checkpoint.limb_threshold)
+                .with_pair_sums(pair_sums);
 
                 if let Err(e) = new_checkpoint.save(file) {
                     eprintln!("Warning: Failed to save checkpoint: {}", e);
                 } else {
-                    progress_callback(iteration_count, &current, start_time.elapsed(), true);
+                    progress_callback(iteration_count, current.len(), start_time.elapsed(), true);
                     last_checkpoint = iteration_count;
                     if should_show_progress {
                         last_progress_report = iteration_count;
@@ -291,12 +520,26 @@ where
                 }
             }
         } else if should_show_progress {
-            progress_callback(iteration_count, &current, start_time.elapsed(), false);
+            progress_callback(iteration_count, current.len(), start_time.elapsed(), false);
             last_progress_report = iteration_count;
         }
+
+        if stop {
+            return VerifyResult {
+                start_number: checkpoint.start_number,
+                is_palindrome: false,
+                iterations_completed: iteration_count,
+                final_number: Some(current.to_biguint()),
+                is_potential_lychrel: false,
+                total_time: std::time::Duration::from_secs_f64(
+                    base_elapsed + start_time.elapsed().as_secs_f64(),
+                ),
+            };
+        }
     }
 
-    progress_callback(iteration_count, &current, start_time.elapsed(), false);
+    progress_callback(iteration_count, current.len(), start_time.elapsed(), false);
+    let current = current.to_biguint();
     let total_duration =
         std::time::Duration::from_secs_f64(base_elapsed + start_time.elapsed().as_secs_f64());
 
@@ -332,7 +575,7 @@ pub fn resume_from_checkpoint_with_config<F>(
     progress_callback: F,
 ) -> VerifyResult
 where
-    F: FnMut(u64, &BigUint, std::time::Duration, bool),
+    F: FnMut(u64, usize, std::time::Duration, bool),
 {
     resume_from_checkpoint(
         checkpoint,
@@ -1,4 +1,7 @@
-use lychrel_finder::{lychrel_iteration, search_range, SearchConfig};
+use lychrel_finder::{
+    lychrel_iteration, resume_from_checkpoint, search_range, verify_lychrel_resumable, Checkpoint,
+    SearchConfig, VerifyConfig, VerifyEngine,
+};
 use num_bigint::BigUint;
 
 #[test]
@@ -32,9 +35,12 @@ fn test_search_range_finds_multiple_lychrel() {
         start: BigUint::from(190u32),
         end: BigUint::from(200u32),
         max_iterations: 100,
+        radix: 10,
+        classify: false,
         parallel: false,
         checkpoint_interval: None,
         checkpoint_file: None,
+        cache_size: None,
     };
 
     let results = search_range(config);
@@ -55,18 +61,24 @@ fn test_parallel_vs_sequential() {
         start: BigUint::from(1u32),
         end: BigUint::from(100u32),
         max_iterations: 100,
+        radix: 10,
+        classify: false,
         parallel: false,
         checkpoint_interval: None,
         checkpoint_file: None,
+        cache_size: None,
     };
 
     let config_par = SearchConfig {
         start: BigUint::from(1u32),
         end: BigUint::from(100u32),
         max_iterations: 100,
+        radix: 10,
+        classify: false,
         parallel: true,
         checkpoint_interval: None,
         checkpoint_file: None,
+        cache_size: None,
     };
 
     let results_seq = search_range(config_seq);
@@ -87,3 +99,101 @@ fn test_large_number() {
     assert_eq!(result.start_number, large);
     assert!(result.iterations <= 10);
 }
+
+#[test]
+fn test_verify_limb_engine_matches_digit_engine() {
+    let run = |limb_threshold| {
+        let config = VerifyConfig {
+            number: BigUint::from(196u32),
+            max_iterations: 2000,
+            progress_interval: 1000,
+            checkpoint_interval: None,
+            checkpoint_file: None,
+            limb_threshold,
+            engine: VerifyEngine::Standard,
+        };
+        verify_lychrel_resumable(config, |_, _, _, _| {})
+    };
+
+    // 2000 iterations of 196 reach about 830 digits, so 100 switches mid-run
+    let digits = run(usize::MAX);
+    let limbs = run(100);
+    assert_eq!(digits.iterations_completed, limbs.iterations_completed);
+    assert_eq!(digits.final_number, limbs.final_number);
+
+    // Both engines must find the same palindrome
+    let config = VerifyConfig {
+        number: BigUint::from(89u32),
+        max_iterations: 100,
+        progress_interval: 10,
+        checkpoint_interval: None,
+        checkpoint_file: None,
+        limb_threshold: 0,
+        engine: VerifyEngine::Standard,
+    };
+    let result = verify_lychrel_resumable(config, |_, _, _, _| {});
+    assert_eq!(result.iterations_completed, 24);
+    assert_eq!(result.final_number, Some(BigUint::from(8813200023188u64)));
+}
+
+#[test]
+fn test_verify_pair_sum_engine_checkpoint_resume() {
+    let path = std::env::temp_dir().join("lychrel_pair_sum_checkpoint.json");
+    let path = path.to_str().unwrap().to_string();
+    let config = |max_iterations, engine, checkpoint_file| VerifyConfig {
+        number: BigUint::from(196u32),
+        max_iterations,
+        progress_interval: 1000,
+        checkpoint_interval: Some(500),
+        checkpoint_file,
+        limb_threshold: 10_000,
+        engine,
+    };
+
+    let standard =
+        verify_lychrel_resumable(config(1500, VerifyEngine::Standard, None), |_, _, _, _| {});
+    let interrupted = verify_lychrel_resumable(
+        config(1000, VerifyEngine::PairSum, Some(path.clone())),
+        |_, _, _, _| {},
+    );
+
+    let mut checkpoint = Checkpoint::load(&path).unwrap();
+    assert_eq!(checkpoint.iterations_completed, 1000);
+
+    // A start longer than the limb threshold still runs on pair sums
+    let long_start = verify_lychrel_resumable(
+        VerifyConfig {
+            limb_threshold: 2,
+            ..config(1000, VerifyEngine::PairSum, Some(path.clone()))
+        },
+        |_, _, _, _| {},
+    );
+    assert_eq!(long_start.final_number, interrupted.final_number);
+    assert_eq!(
+        Checkpoint::load(&path).unwrap().pair_sums,
+        checkpoint.pair_sums
+    );
+    // Pair-sum checkpoints only hold the pair sums
+    assert!(checkpoint.pair_sums.is_some());
+    assert_eq!(checkpoint.current_number, None);
+    assert_eq!(
+        checkpoint.digit_count(),
+        interrupted.final_number.unwrap().to_string().len()
+    );
+
+    checkpoint.max_iterations = 1500;
+    let resumed = resume_from_checkpoint(checkpoint, None, None, |_, _, _, _| {});
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(resumed.iterations_completed, 1500);
+    assert_eq!(resumed.final_number, standard.final_number);
+
+    // The pair-sum engine finds palindromes too, including the very first step
+    for (start, iterations) in [(89u32, 24u64), (56, 1)] {
+        let mut pair_config = config(100, VerifyEngine::PairSum, None);
+        pair_config.number = BigUint::from(start);
+        let result = verify_lychrel_resumable(pair_config, |_, _, _, _| {});
+        assert!(result.is_palindrome);
+        assert_eq!(result.iterations_completed, iterations);
+    }
+}
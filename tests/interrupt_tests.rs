@@ -0,0 +1,110 @@
+// The stop flag is global to the process, so everything that depends on it
+// runs in this one test, in its own test binary.
+
+use lychrel_finder::interrupt::{clear_stop, request_stop};
+use lychrel_finder::search_checkpoint::records_file;
+use lychrel_finder::{
+    resume_from_checkpoint, resume_search_from_checkpoint, search_range, search_range_resumable,
+    verify_lychrel_resumable, Checkpoint, HuntConfig, RecordHuntCheckpoint, RecordHunter,
+    SearchCheckpoint, SearchConfig, StopReason, VerifyConfig, VerifyEngine,
+};
+use num_bigint::BigUint;
+
+fn temp_file(name: &str) -> String {
+    std::env::temp_dir()
+        .join(name)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+fn search_config(parallel: bool, checkpoint_file: &str) -> SearchConfig {
+    SearchConfig {
+        start: BigUint::from(1u32),
+        end: BigUint::from(3000u32),
+        max_iterations: 100,
+        radix: 10,
+        classify: false,
+        parallel,
+        checkpoint_interval: None,
+        checkpoint_file: Some(checkpoint_file.to_string()),
+        cache_size: None,
+    }
+}
+
+#[test]
+fn test_stop_request_saves_final_checkpoint() {
+    // Searches stop early and resume to the uninterrupted results
+    for parallel in [false, true] {
+        let file = temp_file(&format!("lychrel_interrupt_search_{}.json", parallel));
+        let full = search_range(SearchConfig {
+            checkpoint_file: None,
+            ..search_config(parallel, &file)
+        });
+
+        request_stop();
+        let stopped = search_range_resumable(search_config(parallel, &file), |_, _, _| {});
+        clear_stop();
+        assert!(stopped.total_tested < full.total_tested);
+
+        let checkpoint = SearchCheckpoint::load(&file).unwrap();
+        assert_eq!(checkpoint.numbers_tested, stopped.total_tested);
+        let resumed = resume_search_from_checkpoint(checkpoint, |_, _, _| {});
+        std::fs::remove_file(&file).ok();
+        std::fs::remove_file(records_file(&file)).ok();
+        assert_eq!(resumed, full);
+    }
+
+    // Verification stops after one iteration with a checkpoint of it
+    let file = temp_file("lychrel_interrupt_verify.json");
+    request_stop();
+    let stopped = verify_lychrel_resumable(
+        VerifyConfig {
+            number: BigUint::from(196u32),
+            max_iterations: 1000,
+            progress_interval: 100,
+            checkpoint_interval: None,
+            checkpoint_file: Some(file.clone()),
+            limb_threshold: 10_000,
+            engine: VerifyEngine::Standard,
+        },
+        |_, _, _, _| {},
+    );
+    clear_stop();
+    assert_eq!(stopped.iterations_completed, 1);
+    assert!(!stopped.is_potential_lychrel);
+
+    let checkpoint = Checkpoint::load(&file).unwrap();
+    assert_eq!(checkpoint.iterations_completed, 1);
+    let resumed = resume_from_checkpoint(checkpoint, None, None, |_, _, _, _| {});
+    std::fs::remove_file(&file).ok();
+    assert_eq!(resumed.iterations_completed, 1000);
+    assert!(resumed.is_potential_lychrel);
+
+    // A hunt stops before its next batch with its checkpoint and cache saved
+    let file = temp_file("lychrel_interrupt_hunt.json");
+    let config = HuntConfig {
+        min_digits: 3,
+        max_digits: Some(4),
+        checkpoint_file: file.clone(),
+        ..HuntConfig::default()
+    };
+    request_stop();
+    let stopped = RecordHunter::new(config.clone()).hunt();
+    clear_stop();
+    assert_eq!(stopped.stop_reason, StopReason::Interrupted);
+    assert_eq!(stopped.numbers_tested, 0);
+
+    let checkpoint = RecordHuntCheckpoint::load(std::path::Path::new(&file)).unwrap();
+    assert_eq!(checkpoint.statistics.numbers_tested, 0);
+    assert!(std::path::Path::new(&checkpoint.thread_cache_file).exists());
+
+    // Resumed from that checkpoint, the hunt finishes its range
+    let resumed = RecordHunter::from_checkpoint(config, &checkpoint)
+        .unwrap()
+        .hunt();
+    std::fs::remove_file(&checkpoint.thread_cache_file).ok();
+    std::fs::remove_file(&file).ok();
+    assert_eq!(resumed.stop_reason, StopReason::RangeExhausted);
+    assert_eq!(resumed.numbers_tested, 9900);
+}